@@ -54,7 +54,7 @@ fn http_parser<'a>() -> impl Parse<'a, Output = Request<'a>> {
         .map(|builder| builder.build())
 }
 
-fn parse_request<'a>() -> Cell<impl Parse<'a, Output = (&'a str, &'a str, &'a str)>> {
+fn parse_request<'a>() -> Cell<'a, impl Parse<'a, Output = (&'a str, &'a str, &'a str)>> {
     let method = slice(b"GET")
         .or(slice(b"HEAD"))
         .or(slice(b"POST"))
@@ -78,7 +78,7 @@ fn parse_request<'a>() -> Cell<impl Parse<'a, Output = (&'a str, &'a str, &'a st
         .map(|((a, b), c)| (a, b, c))
 }
 
-fn parse_headers<'a>() -> Cell<impl Parse<'a, Output = Vec<(&'a str, &'a str)>>> {
+fn parse_headers<'a>() -> Cell<'a, impl Parse<'a, Output = Vec<(&'a str, &'a str)>>> {
     let header = take_until(chr(':'))
         .skip(slice(b": "))
         .then(take_until(slice(b"\r\n")))
@@ -110,5 +110,46 @@ fn http_bench(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, http_bench);
+fn method_bench(c: &mut Criterion) {
+    let chained = slice(b"GET")
+        .or(slice(b"HEAD"))
+        .or(slice(b"POST"))
+        .or(slice(b"PUT"))
+        .or(slice(b"DELETE"))
+        .or(slice(b"CONNECT"))
+        .or(slice(b"OPTIONS"))
+        .or(slice(b"TRACE"))
+        .or(slice(b"PATCH"));
+    let trie = one_of_literals(&[
+        b"GET", b"HEAD", b"POST", b"PUT", b"DELETE", b"CONNECT", b"OPTIONS", b"TRACE", b"PATCH",
+    ]);
+    let mut group = c.benchmark_group("method");
+
+    // First and last alternative of the chain.
+    let lines = [
+        ("GET", "GET /index.html HTTP/1.1\r\n"),
+        ("PATCH", "PATCH /index.html HTTP/1.1\r\n"),
+    ];
+
+    for &(method, line) in &lines {
+        let bytes = black_box(line.as_bytes());
+
+        group.bench_function(format!("or-{}", method), |b| {
+            b.iter(|| {
+                let res = chained.parse(bytes);
+                assert!(res.is_ok());
+            })
+        });
+        group.bench_function(format!("one-of-literals-{}", method), |b| {
+            b.iter(|| {
+                let res = trie.parse(bytes);
+                assert!(res.is_ok());
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, http_bench, method_bench);
 criterion_main!(benches);
@@ -1,14 +1,22 @@
+use crate::error::ParseError;
+use crate::parser::simd::{TakeTillAny, TakeUntilLiteral, TakeWhileSet};
 use crate::parser::*;
+use crate::simd::ByteSet;
+use crate::stream::{AsBytes, Input, IntoInput};
+use std::marker::PhantomData;
+use std::ops::{Add, BitAnd, BitOr, Shl, Shr};
 
-pub struct Cell<P> {
+pub struct Cell<'a, P, I = &'a [u8]> {
     parser: P,
+    marker: PhantomData<fn(I) -> &'a ()>,
 }
 
-impl<'a, P> Cell<P> {
+impl<'a, P, I> Cell<'a, P, I> {
     #[inline]
     pub fn new(parser: P) -> Self {
         Self {
             parser,
+            marker: PhantomData,
         }
     }
 
@@ -17,71 +25,496 @@ impl<'a, P> Cell<P> {
         self.parser
     }
 
+    /// Runs the parser, also accepting inputs such as `b"..."` literals that
+    /// only coerce to the input type.
     #[inline]
-    pub fn map<B, F>(self, f: F) -> Cell<Map<P, F>>
+    pub fn parse<J>(&self, input: J) -> Result<(I, P::Output), ParseError>
+    where
+        P: Parse<'a, I>,
+        J: IntoInput<Input = I>,
+    {
+        self.parser.parse(input.into_input())
+    }
+
+    #[inline]
+    pub fn map<B, F>(self, f: F) -> Cell<'a, Map<P, F>, I>
     where
         F: Fn(P::Output) -> B,
-        P: Parse<'a>
+        P: Parse<'a, I>
     {
         Cell::new(Map::new(self.take(), f))
     }
 
     #[inline]
-    pub fn or<RHS>(self, rhs: Cell<RHS>) -> Cell<Or<P, RHS>>
+    pub fn or<RHS>(self, rhs: RHS) -> Cell<'a, Or<P, RHS>, I>
+    where
+        RHS: Parse<'a, I, Output = P::Output>,
+        P: Parse<'a, I>
+    {
+        Cell::new(Or::new(self.take(), rhs))
+    }
+
+    #[inline]
+    pub fn then<RHS>(self, rhs: RHS) -> Cell<'a, And<P, RHS>, I>
+    where
+        RHS: Parse<'a, I>,
+    {
+        Cell::new(And::new(self.take(), rhs))
+    }
+
+    #[inline]
+    pub fn skip<RHS>(self, rhs: RHS) -> Cell<'a, Skip<P, RHS>, I>
+    where
+        RHS: Parse<'a, I>,
+    {
+        Cell::new(Skip::new(self.take(), rhs))
+    }
+
+    #[inline]
+    pub fn opt(self) -> Cell<'a, Opt<P>, I>
+    where
+        P: Parse<'a, I>,
+    {
+        Cell::new(Opt::new(self.take()))
+    }
+
+    #[inline]
+    pub fn many_m_n(self, min: usize, max: usize) -> Cell<'a, ManyMN<P>, I>
+    where
+        P: Parse<'a, I>,
+    {
+        Cell::new(ManyMN::new(self.take(), min, max))
+    }
+
+    #[inline]
+    pub fn count(self, count: usize) -> Cell<'a, Count<P>, I>
+    where
+        P: Parse<'a, I>,
+    {
+        Cell::new(Count::new(self.take(), count))
+    }
+
+    #[inline]
+    pub fn sep_by0<S>(self, sep: S) -> Cell<'a, SepBy0<P, S>, I>
+    where
+        P: Parse<'a, I>,
+        S: Parse<'a, I>,
+    {
+        Cell::new(SepBy0::new(self.take(), sep))
+    }
+
+    #[inline]
+    pub fn sep_by1<S>(self, sep: S) -> Cell<'a, SepBy1<P, S>, I>
+    where
+        P: Parse<'a, I>,
+        S: Parse<'a, I>,
+    {
+        Cell::new(SepBy1::new(self.take(), sep))
+    }
+
+    #[inline]
+    pub fn sep_end_by<S>(self, sep: S) -> Cell<'a, SepEndBy<P, S>, I>
     where
-        RHS: Parse<'a, Output = P::Output>,
-        P: Parse<'a>
+        P: Parse<'a, I>,
+        S: Parse<'a, I>,
     {
-        Cell::new(Or::new(self.take(), rhs.take()))
+        Cell::new(SepEndBy::new(self.take(), sep))
     }
 
     #[inline]
-    pub fn then<RHS>(self, rhs: Cell<RHS>) -> Cell<And<P, RHS>>
+    pub fn many_till<E>(self, end: E) -> Cell<'a, ManyTill<P, E>, I>
     where
-        RHS: Parse<'a>,
+        P: Parse<'a, I>,
+        E: Parse<'a, I>,
     {
-        Cell::new(And::new(self.take(), rhs.take()))
+        Cell::new(ManyTill::new(self.take(), end))
     }
 
     #[inline]
-    pub fn skip<RHS>(self, rhs: Cell<RHS>) -> Cell<Skip<P, RHS>>
+    pub fn fold_many0<B, Init, F>(self, init: Init, f: F) -> Cell<'a, FoldMany0<P, Init, F>, I>
     where
-        RHS: Parse<'a>,
+        P: Parse<'a, I>,
+        Init: Fn() -> B,
+        F: Fn(B, P::Output) -> B,
     {
-        Cell::new(Skip::new(self.take(), rhs.take()))
+        Cell::new(FoldMany0::new(self.take(), init, f))
     }
 
     #[inline]
-    pub fn skip_left<RHS>(self, rhs: Cell<RHS>) -> Cell<Skip<RHS, P>>
+    pub fn boxed(self) -> Cell<'a, Boxed<'a, P::Output, I>, I>
     where
-        RHS: Parse<'a>,
+        P: Parse<'a, I> + 'a,
     {
-        Cell::new(Skip::new(rhs.take(), self.take()))
+        Cell::new(Boxed::new(self.take()))
+    }
+
+    #[inline]
+    pub fn cut(self) -> Cell<'a, Cut<P>, I>
+    where
+        P: Parse<'a, I>,
+    {
+        Cell::new(Cut::new(self.take()))
+    }
+
+    #[inline]
+    pub fn spanned(self) -> Cell<'a, Spanned<P>, I>
+    where
+        P: Parse<'a, I>,
+    {
+        Cell::new(Spanned::new(self.take()))
+    }
+
+    #[inline]
+    pub fn recognize(self) -> Cell<'a, Recognize<P>, I>
+    where
+        P: Parse<'a, I>,
+    {
+        Cell::new(Recognize::new(self.take()))
+    }
+
+    #[inline]
+    pub fn skip_left<RHS>(self, rhs: RHS) -> Cell<'a, Skip<RHS, P>, I>
+    where
+        RHS: Parse<'a, I>,
+    {
+        Cell::new(Skip::new(rhs, self.take()))
+    }
+
+    #[inline]
+    pub fn map_res<B, E, F>(self, f: F) -> Cell<'a, MapRes<P, F>, I>
+    where
+        P: Parse<'a, I>,
+        F: Fn(P::Output) -> Result<B, E>,
+    {
+        Cell::new(MapRes::new(self.take(), f))
+    }
+
+    #[inline]
+    pub fn and_then<RHS>(self, rhs: RHS) -> Cell<'a, AndThen<P, RHS>, I>
+    where
+        P: Parse<'a, I>,
+        P::Output: Input + AsBytes,
+        RHS: Parse<'a, P::Output>,
+    {
+        Cell::new(AndThen::new(self.take(), rhs))
+    }
+
+    #[inline]
+    pub fn flat_map<RHS, F>(self, f: F) -> Cell<'a, FlatMap<P, F>, I>
+    where
+        P: Parse<'a, I>,
+        F: Fn(P::Output) -> RHS,
+        RHS: Parse<'a, I>,
+    {
+        Cell::new(FlatMap::new(self.take(), f))
+    }
+
+    #[inline]
+    pub fn value<T: Clone>(self, value: T) -> Cell<'a, Value<P, T>, I> {
+        Cell::new(Value::new(self.take(), value))
+    }
+
+    #[inline]
+    pub fn verify<F>(self, f: F) -> Cell<'a, Verify<P, F>, I>
+    where
+        P: Parse<'a, I>,
+        F: Fn(&P::Output) -> bool,
+    {
+        Cell::new(Verify::new(self.take(), f))
+    }
+
+    #[inline]
+    pub fn context(self, context: &'static str) -> Cell<'a, Context<P>, I> {
+        Cell::new(Context::new(self.take(), context))
+    }
+
+    #[inline]
+    pub fn many0(self) -> Cell<'a, Many0<P>, I> {
+        Cell::new(Many0::new(self.take()))
+    }
+
+    #[inline]
+    pub fn many1(self) -> Cell<'a, Many1<P>, I> {
+        Cell::new(Many1::new(self.take()))
     }
 }
 
-impl<'a, P> Parse<'a> for Cell<P>
+/// `a | b` is `a.or(b)`.
+impl<'a, P, RHS, I> BitOr<RHS> for Cell<'a, P, I>
 where
-    P: Parse<'a>,
+    RHS: Parse<'a, I>,
+{
+    type Output = Cell<'a, Or<P, RHS>, I>;
+
+    #[inline]
+    fn bitor(self, rhs: RHS) -> Self::Output {
+        Cell::new(Or::new(self.take(), rhs))
+    }
+}
+
+/// `a & b` is `a.then(b)`.
+impl<'a, P, RHS, I> BitAnd<RHS> for Cell<'a, P, I>
+where
+    RHS: Parse<'a, I>,
+{
+    type Output = Cell<'a, And<P, RHS>, I>;
+
+    #[inline]
+    fn bitand(self, rhs: RHS) -> Self::Output {
+        self.then(rhs)
+    }
+}
+
+/// `a + b` is `a.then(b)`.
+impl<'a, P, RHS, I> Add<RHS> for Cell<'a, P, I>
+where
+    RHS: Parse<'a, I>,
+{
+    type Output = Cell<'a, And<P, RHS>, I>;
+
+    #[inline]
+    fn add(self, rhs: RHS) -> Self::Output {
+        self.then(rhs)
+    }
+}
+
+/// `a >> b` parses both and keeps the output of `b`.
+impl<'a, P, RHS, I> Shr<RHS> for Cell<'a, P, I>
+where
+    RHS: Parse<'a, I>,
+{
+    type Output = Cell<'a, SkipLeft<P, RHS>, I>;
+
+    #[inline]
+    fn shr(self, rhs: RHS) -> Self::Output {
+        Cell::new(SkipLeft::new(self.take(), rhs))
+    }
+}
+
+/// `a << b` parses both and keeps the output of `a`.
+impl<'a, P, RHS, I> Shl<RHS> for Cell<'a, P, I>
+where
+    RHS: Parse<'a, I>,
+{
+    type Output = Cell<'a, Skip<P, RHS>, I>;
+
+    #[inline]
+    fn shl(self, rhs: RHS) -> Self::Output {
+        self.skip(rhs)
+    }
+}
+
+impl<'a, P: Clone, I> Clone for Cell<'a, P, I> {
+    fn clone(&self) -> Self {
+        Cell::new(self.parser.clone())
+    }
+}
+
+impl<'a, P, I> Parse<'a, I> for Cell<'a, P, I>
+where
+    P: Parse<'a, I>,
 {
     type Output = P::Output;
 
     #[inline]
-    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
         self.parser.parse(input)
     }
 }
 
+/// Combinator methods for every parser, so parsers that are not wrapped in a
+/// [`Cell`] compose the same way.
+///
+/// The results are cells, which carry the input type for inference and
+/// support the operators. `Cell` has inherent methods of the same names that
+/// unwrap the receiver instead of nesting it.
+pub trait ParseExt<'a, I = &'a [u8]>: Parse<'a, I> + Sized {
+    #[inline]
+    fn map<B, F>(self, f: F) -> Cell<'a, Map<Self, F>, I>
+    where
+        F: Fn(Self::Output) -> B,
+    {
+        Cell::new(Map::new(self, f))
+    }
+
+    #[inline]
+    fn map_res<B, E, F>(self, f: F) -> Cell<'a, MapRes<Self, F>, I>
+    where
+        F: Fn(Self::Output) -> Result<B, E>,
+    {
+        Cell::new(MapRes::new(self, f))
+    }
+
+    #[inline]
+    fn and_then<P>(self, parser: P) -> Cell<'a, AndThen<Self, P>, I>
+    where
+        Self::Output: Input + AsBytes,
+        P: Parse<'a, Self::Output>,
+    {
+        Cell::new(AndThen::new(self, parser))
+    }
+
+    #[inline]
+    fn flat_map<P, F>(self, f: F) -> Cell<'a, FlatMap<Self, F>, I>
+    where
+        F: Fn(Self::Output) -> P,
+        P: Parse<'a, I>,
+    {
+        Cell::new(FlatMap::new(self, f))
+    }
+
+    #[inline]
+    fn value<T: Clone>(self, value: T) -> Cell<'a, Value<Self, T>, I> {
+        Cell::new(Value::new(self, value))
+    }
+
+    #[inline]
+    fn verify<F>(self, f: F) -> Cell<'a, Verify<Self, F>, I>
+    where
+        F: Fn(&Self::Output) -> bool,
+    {
+        Cell::new(Verify::new(self, f))
+    }
+
+    #[inline]
+    fn context(self, context: &'static str) -> Cell<'a, Context<Self>, I> {
+        Cell::new(Context::new(self, context))
+    }
+
+    #[inline]
+    fn or<P>(self, parser: P) -> Cell<'a, Or<Self, P>, I>
+    where
+        P: Parse<'a, I, Output = Self::Output>,
+    {
+        Cell::new(Or::new(self, parser))
+    }
+
+    #[inline]
+    fn then<P>(self, parser: P) -> Cell<'a, And<Self, P>, I>
+    where
+        P: Parse<'a, I>,
+    {
+        Cell::new(And::new(self, parser))
+    }
+
+    #[inline]
+    fn skip<P>(self, parser: P) -> Cell<'a, Skip<Self, P>, I>
+    where
+        P: Parse<'a, I>,
+    {
+        Cell::new(Skip::new(self, parser))
+    }
+
+    #[inline]
+    fn skip_left<P>(self, parser: P) -> Cell<'a, Skip<P, Self>, I>
+    where
+        P: Parse<'a, I>,
+    {
+        Cell::new(Skip::new(parser, self))
+    }
+
+    #[inline]
+    fn opt(self) -> Cell<'a, Opt<Self>, I> {
+        Cell::new(Opt::new(self))
+    }
+
+    #[inline]
+    fn many0(self) -> Cell<'a, Many0<Self>, I> {
+        Cell::new(Many0::new(self))
+    }
+
+    #[inline]
+    fn many1(self) -> Cell<'a, Many1<Self>, I> {
+        Cell::new(Many1::new(self))
+    }
+
+    #[inline]
+    fn many_m_n(self, min: usize, max: usize) -> Cell<'a, ManyMN<Self>, I> {
+        Cell::new(ManyMN::new(self, min, max))
+    }
+
+    #[inline]
+    fn count(self, count: usize) -> Cell<'a, Count<Self>, I> {
+        Cell::new(Count::new(self, count))
+    }
+
+    #[inline]
+    fn sep_by0<S>(self, sep: S) -> Cell<'a, SepBy0<Self, S>, I>
+    where
+        S: Parse<'a, I>,
+    {
+        Cell::new(SepBy0::new(self, sep))
+    }
+
+    #[inline]
+    fn sep_by1<S>(self, sep: S) -> Cell<'a, SepBy1<Self, S>, I>
+    where
+        S: Parse<'a, I>,
+    {
+        Cell::new(SepBy1::new(self, sep))
+    }
+
+    #[inline]
+    fn sep_end_by<S>(self, sep: S) -> Cell<'a, SepEndBy<Self, S>, I>
+    where
+        S: Parse<'a, I>,
+    {
+        Cell::new(SepEndBy::new(self, sep))
+    }
+
+    #[inline]
+    fn many_till<E>(self, end: E) -> Cell<'a, ManyTill<Self, E>, I>
+    where
+        E: Parse<'a, I>,
+    {
+        Cell::new(ManyTill::new(self, end))
+    }
+
+    #[inline]
+    fn fold_many0<B, Init, F>(self, init: Init, f: F) -> Cell<'a, FoldMany0<Self, Init, F>, I>
+    where
+        Init: Fn() -> B,
+        F: Fn(B, Self::Output) -> B,
+    {
+        Cell::new(FoldMany0::new(self, init, f))
+    }
+
+    #[inline]
+    fn boxed(self) -> Cell<'a, Boxed<'a, Self::Output, I>, I>
+    where
+        Self: 'a,
+    {
+        Cell::new(Boxed::new(self))
+    }
+
+    #[inline]
+    fn cut(self) -> Cell<'a, Cut<Self>, I> {
+        Cell::new(Cut::new(self))
+    }
+
+    #[inline]
+    fn spanned(self) -> Cell<'a, Spanned<Self>, I> {
+        Cell::new(Spanned::new(self))
+    }
+
+    #[inline]
+    fn recognize(self) -> Cell<'a, Recognize<Self>, I> {
+        Cell::new(Recognize::new(self))
+    }
+}
+
+impl<'a, I, P> ParseExt<'a, I> for P where P: Parse<'a, I> {}
+
 #[inline]
-pub fn cell<'a, P>(parser: P) -> Cell<P>
+pub fn cell<'a, P, I>(parser: P) -> Cell<'a, P, I>
 where
-    P: Parse<'a>,
+    P: Parse<'a, I>,
 {
     Cell::new(parser)
 }
 
 #[inline]
-pub fn state<'a, F, T>(f: F) -> Cell<State<F>>
+pub fn state<'a, F, T, I>(f: F) -> Cell<'a, State<F>, I>
 where
     F: Fn() -> T,
 {
@@ -89,82 +522,265 @@ where
 }
 
 #[inline]
-pub fn many0<'a, P>(parser: P) -> Cell<Many0<P>>
+pub fn many0<'a, P, I>(parser: P) -> Cell<'a, Many0<P>, I>
 where
-    P: Parse<'a>,
+    P: Parse<'a, I>,
 {
     Cell::new(Many0::new(parser))
 }
 
 #[inline]
-pub fn many1<'a, P>(parser: P) -> Cell<Many1<P>>
+pub fn many1<'a, P, I>(parser: P) -> Cell<'a, Many1<P>, I>
 where
-    P: Parse<'a>,
+    P: Parse<'a, I>,
 {
     Cell::new(Many1::new(parser))
 }
 
 #[inline]
-pub fn skip<'a, P1, P2>(p1: P1, p2: P2) -> Cell<Skip<P1, P2>>
+pub fn many_m_n<'a, P, I>(parser: P, min: usize, max: usize) -> Cell<'a, ManyMN<P>, I>
+where
+    P: Parse<'a, I>,
+{
+    Cell::new(ManyMN::new(parser, min, max))
+}
+
+#[inline]
+pub fn count<'a, P, I>(parser: P, count: usize) -> Cell<'a, Count<P>, I>
+where
+    P: Parse<'a, I>,
+{
+    Cell::new(Count::new(parser, count))
+}
+
+#[inline]
+pub fn opt<'a, P, I>(parser: P) -> Cell<'a, Opt<P>, I>
+where
+    P: Parse<'a, I>,
+{
+    Cell::new(Opt::new(parser))
+}
+
+#[inline]
+pub fn sep_by0<'a, P, S, I>(parser: P, sep: S) -> Cell<'a, SepBy0<P, S>, I>
+where
+    P: Parse<'a, I>,
+    S: Parse<'a, I>,
+{
+    Cell::new(SepBy0::new(parser, sep))
+}
+
+#[inline]
+pub fn sep_by1<'a, P, S, I>(parser: P, sep: S) -> Cell<'a, SepBy1<P, S>, I>
+where
+    P: Parse<'a, I>,
+    S: Parse<'a, I>,
+{
+    Cell::new(SepBy1::new(parser, sep))
+}
+
+#[inline]
+pub fn sep_end_by<'a, P, S, I>(parser: P, sep: S) -> Cell<'a, SepEndBy<P, S>, I>
 where
-    P1: Parse<'a>,
-    P2: Parse<'a>,
+    P: Parse<'a, I>,
+    S: Parse<'a, I>,
+{
+    Cell::new(SepEndBy::new(parser, sep))
+}
+
+#[inline]
+pub fn many_till<'a, P, E, I>(parser: P, end: E) -> Cell<'a, ManyTill<P, E>, I>
+where
+    P: Parse<'a, I>,
+    E: Parse<'a, I>,
+{
+    Cell::new(ManyTill::new(parser, end))
+}
+
+#[inline]
+pub fn fold_many0<'a, P, Init, F, B, I>(
+    parser: P,
+    init: Init,
+    f: F,
+) -> Cell<'a, FoldMany0<P, Init, F>, I>
+where
+    P: Parse<'a, I>,
+    Init: Fn() -> B,
+    F: Fn(B, P::Output) -> B,
+{
+    Cell::new(FoldMany0::new(parser, init, f))
+}
+
+#[inline]
+pub fn skip<'a, P1, P2, I>(p1: P1, p2: P2) -> Cell<'a, Skip<P1, P2>, I>
+where
+    P1: Parse<'a, I>,
+    P2: Parse<'a, I>,
 {
     Cell::new(Skip::new(p1, p2))
 }
 
 #[inline]
-pub fn skip_left<'a, P1, P2>(p1: P1, p2: P2) -> Cell<Skip<P2, P1>>
+pub fn skip_left<'a, P1, P2, I>(p1: P1, p2: P2) -> Cell<'a, Skip<P2, P1>, I>
 where
-    P1: Parse<'a>,
-    P2: Parse<'a>,
+    P1: Parse<'a, I>,
+    P2: Parse<'a, I>,
 {
     Cell::new(Skip::new(p2, p1))
 }
 
 #[inline]
-pub fn take_until<'a, P>(parser: P) -> Cell<TakeUntil<P>>
+pub fn take_until<'a, P, I>(parser: P) -> Cell<'a, TakeUntil<P>, I>
 where
-    P: Parse<'a>,
+    P: Parse<'a, I>,
 {
     Cell::new(TakeUntil::new(parser))
 }
 
 #[inline]
-pub fn any_char<'a>() -> Cell<AnyChar> {
+pub fn lazy<'a, F, P, I>(f: F) -> Cell<'a, Lazy<F>, I>
+where
+    F: Fn() -> P,
+    P: Parse<'a, I>,
+{
+    Cell::new(Lazy::new(f))
+}
+
+/// Defines a parser that can refer to itself through the cell passed to
+/// `f`, for nested grammars such as arrays or parenthesized expressions.
+#[inline]
+pub fn recursive<'a, O, F, P, I>(f: F) -> Cell<'a, Recursive<'a, O, I>, I>
+where
+    F: FnOnce(Cell<'a, Recursive<'a, O, I>, I>) -> P,
+    P: Parse<'a, I, Output = O> + 'a,
+{
+    Cell::new(Recursive::new(|handle| f(Cell::new(handle))))
+}
+
+#[inline]
+pub fn cut<'a, P, I>(parser: P) -> Cell<'a, Cut<P>, I>
+where
+    P: Parse<'a, I>,
+{
+    Cell::new(Cut::new(parser))
+}
+
+#[inline]
+pub fn spanned<'a, P, I>(parser: P) -> Cell<'a, Spanned<P>, I>
+where
+    P: Parse<'a, I>,
+{
+    Cell::new(Spanned::new(parser))
+}
+
+#[inline]
+pub fn recognize<'a, P, I>(parser: P) -> Cell<'a, Recognize<P>, I>
+where
+    P: Parse<'a, I>,
+{
+    Cell::new(Recognize::new(parser))
+}
+
+#[inline]
+pub fn any_char<'a, I>() -> Cell<'a, AnyChar, I>
+where
+    AnyChar: Parse<'a, I>,
+{
     Cell::new(AnyChar::new())
 }
 
 #[inline]
-pub fn any_digit<'a>() -> Cell<AnyDigit> {
+pub fn any_digit<'a, I>() -> Cell<'a, AnyDigit, I>
+where
+    AnyDigit: Parse<'a, I>,
+{
     Cell::new(AnyDigit::new())
 }
 
 #[inline]
-pub fn byte<'a>(byte: u8) -> Cell<Byte> {
+pub fn byte<'a, I>(byte: u8) -> Cell<'a, Byte, I>
+where
+    Byte: Parse<'a, I>,
+{
     Cell::new(Byte::new(byte))
 }
 
 #[inline]
-pub fn chr<'a>(ch: char) -> Cell<Char> {
+pub fn chr<'a, I>(ch: char) -> Cell<'a, Char, I>
+where
+    Char: Parse<'a, I>,
+{
     Cell::new(Char::new(ch))
 }
 
 #[inline]
-pub fn slice<'a>(bytes: &[u8]) -> Cell<Slice> {
+pub fn slice<'a, I>(bytes: &[u8]) -> Cell<'a, Slice, I>
+where
+    Slice: Parse<'a, I>,
+{
     Cell::new(Slice::new(bytes))
 }
 
-#[cfg(all(
-    any(target_arch = "x86", target_arch = "x86_64"),
-    target_feature = "sse4.2"
-))]
-pub mod simd {
-    use super::*;
-    use crate::parser::simd::*;
+#[inline]
+pub fn one_of_literals<'a, I>(literals: &[&[u8]]) -> Cell<'a, OneOfLiterals, I>
+where
+    OneOfLiterals: Parse<'a, I>,
+{
+    Cell::new(OneOfLiterals::new(literals))
+}
 
-    #[inline]
-    pub fn take_until_literal<'a>(bytes: &[u8]) -> Cell<'a, TakeUntilLiteral> {
-        Cell::new(TakeUntilLiteral::new(bytes))
-    }
+#[inline]
+pub fn keywords<'a, T, I>(keywords: &[(&[u8], T)]) -> Cell<'a, Keywords<T>, I>
+where
+    T: Clone,
+    Keywords<T>: Parse<'a, I>,
+{
+    Cell::new(Keywords::new(keywords))
+}
+
+#[inline]
+pub fn just<'a, T, I>(token: T) -> Cell<'a, Just<T>, I>
+where
+    Just<T>: Parse<'a, I>,
+{
+    Cell::new(Just::new(token))
+}
+
+#[inline]
+pub fn satisfy<'a, F, I>(f: F) -> Cell<'a, Satisfy<F>, I>
+where
+    Satisfy<F>: Parse<'a, I>,
+{
+    Cell::new(Satisfy::new(f))
+}
+
+#[inline]
+pub fn take_until_literal<'a, I>(bytes: &[u8]) -> Cell<'a, TakeUntilLiteral, I>
+where
+    TakeUntilLiteral: Parse<'a, I>,
+{
+    Cell::new(TakeUntilLiteral::new(bytes))
+}
+
+#[inline]
+pub fn take_while_set<'a, I>(set: ByteSet) -> Cell<'a, TakeWhileSet, I>
+where
+    TakeWhileSet: Parse<'a, I>,
+{
+    Cell::new(TakeWhileSet::new(set))
+}
+
+#[inline]
+pub fn take_till_any<'a, I>(set: ByteSet) -> Cell<'a, TakeTillAny, I>
+where
+    TakeTillAny: Parse<'a, I>,
+{
+    Cell::new(TakeTillAny::new(set))
+}
+
+/// Former home of the SIMD parsers, from when they needed SSE4.2 at compile
+/// time.
+#[deprecated(note = "use the functions at the top of `cell` instead")]
+pub mod simd {
+    pub use super::{take_till_any, take_until_literal, take_while_set};
 }
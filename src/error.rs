@@ -0,0 +1,276 @@
+use crate::stream::{Input, Token};
+use crate::unicode::Category;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expected {
+    Byte(u8),
+    Char(char),
+    Slice(Box<[u8]>),
+    Token(String),
+    Alphabetic,
+    Digit,
+    Scalar,
+    Category(Category),
+    Range(char, char),
+    NoneOf(Box<[char]>),
+}
+
+impl From<Found> for Expected {
+    fn from(found: Found) -> Self {
+        match found {
+            Found::Byte(byte) => Expected::Byte(byte),
+            Found::Char(ch) => Expected::Char(ch),
+            Found::Token(token) => Expected::Token(token),
+        }
+    }
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Byte(byte) => write!(f, "'{}'", byte.escape_ascii()),
+            Expected::Char(ch) => write!(f, "{:?}", ch),
+            Expected::Slice(bytes) => write!(f, "\"{}\"", bytes.escape_ascii()),
+            Expected::Token(token) => write!(f, "{}", token),
+            Expected::Alphabetic => write!(f, "alphabetic character"),
+            Expected::Digit => write!(f, "digit"),
+            Expected::Scalar => write!(f, "character"),
+            Expected::Category(category) => write!(f, "{}", category),
+            Expected::Range(start, end) => write!(f, "{:?}..={:?}", start, end),
+            Expected::NoneOf(chars) => {
+                write!(f, "character other than ")?;
+                for (idx, ch) in chars.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{:?}", ch)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Item of the input at the position of an error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Found {
+    Byte(u8),
+    Char(char),
+    Token(String),
+}
+
+impl fmt::Display for Found {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Found::Byte(byte) => write!(f, "'{}'", byte.escape_ascii()),
+            Found::Char(ch) => write!(f, "{:?}", ch),
+            Found::Token(token) => write!(f, "{}", token),
+        }
+    }
+}
+
+/// Amount of additional input a streaming parser needs to make progress.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Needed {
+    Unknown,
+    Size(usize),
+}
+
+/// Error returned by a failed parse.
+///
+/// The failure position is stored as the length of the input that was left
+/// when the error occurred, use [`ParseError::offset`] with the original
+/// input to recover the offset.
+///
+/// Streaming parsers that run out of input return an incomplete error
+/// instead, which combinators propagate without backtracking so the caller
+/// can buffer more bytes and retry. Errors raised past a [`cut`] are fatal
+/// and are propagated the same way, so alternatives and repetitions report
+/// them rather than trying something else.
+///
+/// [`cut`]: crate::cell::cut
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    remaining: usize,
+    expected: Vec<Expected>,
+    found: Option<Found>,
+    needed: Option<Needed>,
+    fatal: bool,
+    context: Vec<&'static str>,
+}
+
+impl ParseError {
+    #[inline]
+    pub fn new<I: Input>(input: I, expected: Expected) -> Self {
+        Self {
+            remaining: input.input_len(),
+            expected: vec![expected],
+            found: input.next_item().map(|(item, _)| item.found()),
+            needed: None,
+            fatal: false,
+            context: Vec::new(),
+        }
+    }
+
+    /// Error for input that ended before `expected` could be decided.
+    #[inline]
+    pub fn incomplete<I: Input>(input: I, needed: Needed, expected: Expected) -> Self {
+        Self {
+            remaining: input.input_len(),
+            expected: vec![expected],
+            found: None,
+            needed: Some(needed),
+            fatal: false,
+            context: Vec::new(),
+        }
+    }
+
+    /// Error at `input` without any particular expectation.
+    #[inline]
+    pub fn unexpected<I: Input>(input: I) -> Self {
+        Self {
+            remaining: input.input_len(),
+            expected: Vec::new(),
+            found: input.next_item().map(|(item, _)| item.found()),
+            needed: None,
+            fatal: false,
+            context: Vec::new(),
+        }
+    }
+
+    #[inline]
+    pub fn offset<I: Input>(&self, input: I) -> usize {
+        input.input_len().saturating_sub(self.remaining)
+    }
+
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    #[inline]
+    pub fn expected(&self) -> &[Expected] {
+        &self.expected
+    }
+
+    #[inline]
+    pub fn found(&self) -> Option<&Found> {
+        self.found.as_ref()
+    }
+
+    #[inline]
+    pub fn needed(&self) -> Option<Needed> {
+        self.needed
+    }
+
+    /// What was being parsed when the error occurred, innermost first.
+    #[inline]
+    pub fn context(&self) -> &[&'static str] {
+        &self.context
+    }
+
+    #[inline]
+    pub fn is_incomplete(&self) -> bool {
+        self.needed.is_some()
+    }
+
+    #[inline]
+    pub fn is_fatal(&self) -> bool {
+        self.fatal
+    }
+
+    /// Whether a combinator may backtrack and try something else.
+    #[inline]
+    pub fn is_recoverable(&self) -> bool {
+        !self.fatal && !self.is_incomplete()
+    }
+
+    /// Marks the error as fatal, stopping any enclosing backtracking.
+    #[inline]
+    pub fn into_fatal(mut self) -> Self {
+        self.fatal = true;
+        self
+    }
+
+    /// Turns an incomplete error into a regular one, for when no more input
+    /// will arrive.
+    #[inline]
+    pub fn into_complete(mut self) -> Self {
+        self.needed = None;
+        self
+    }
+
+    /// Records that the error occurred while parsing `context`.
+    #[inline]
+    pub fn add_context(mut self, context: &'static str) -> Self {
+        self.context.push(context);
+        self
+    }
+
+    /// Moves the error to the position of `input`.
+    #[inline]
+    pub(crate) fn at<I: Input>(mut self, input: I) -> Self {
+        self.remaining = input.input_len();
+        self
+    }
+
+    /// Combines the errors of two alternatives.
+    ///
+    /// A fatal or incomplete error wins since it must not be backtracked
+    /// over, otherwise the error that got further into the input wins and
+    /// errors at the same position have their expected sets merged.
+    pub fn merge(mut self, other: Self) -> Self {
+        if self.is_recoverable() != other.is_recoverable() {
+            return if self.is_recoverable() { other } else { self };
+        }
+
+        if other.remaining < self.remaining {
+            return other;
+        }
+
+        if other.remaining == self.remaining {
+            for expected in other.expected {
+                if !self.expected.contains(&expected) {
+                    self.expected.push(expected);
+                }
+            }
+        }
+
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.expected.as_slice() {
+            [] => write!(f, "unexpected ")?,
+            [expected] => write!(f, "expected {}, found ", expected)?,
+            [first, rest @ ..] => {
+                write!(f, "expected one of {}", first)?;
+                for expected in rest {
+                    write!(f, ", {}", expected)?;
+                }
+                write!(f, ", found ")?;
+            }
+        }
+
+        match &self.found {
+            Some(found) => write!(f, "{}", found),
+            None => write!(f, "end of input"),
+        }?;
+
+        for context in &self.context {
+            write!(f, " in {}", context)?;
+        }
+
+        match self.needed {
+            Some(Needed::Size(1)) => write!(f, " (1 more byte needed)"),
+            Some(Needed::Size(n)) => write!(f, " ({} more bytes needed)", n),
+            Some(Needed::Unknown) => write!(f, " (more input needed)"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,190 @@
+//! Query strings and `application/x-www-form-urlencoded` bodies.
+//!
+//! Decoding follows the WHATWG URL standard: `+` is a space, malformed
+//! percent escapes are kept as they are and invalid UTF-8 is replaced, so
+//! decoding itself never fails. Pairs are split up front but only decoded
+//! as the iterator reaches them.
+//!
+//! ```
+//! use parsing::form;
+//! use parsing::prelude::*;
+//!
+//! let (_, pairs) = form::form().parse(b"name=J%C3%BCrgen+M&lang=de".to_stream()).unwrap();
+//! let pairs: Vec<_> = pairs.collect();
+//!
+//! assert_eq!(pairs[0], ("name".into(), "Jürgen M".into()));
+//! assert_eq!(pairs[1], ("lang".into(), "de".into()));
+//! ```
+
+use crate::cell::Cell;
+use crate::error::{Expected, ParseError};
+use crate::parser::simd::TakeTillAny;
+use crate::parser::{Byte, Parse};
+use crate::simd::ByteSet;
+use crate::uri;
+use std::borrow::Cow;
+use std::collections::hash_map::{Entry, HashMap};
+
+type RawPair<'a> = (&'a [u8], &'a [u8]);
+
+/// What to do with a key that appears more than once.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Duplicates {
+    /// Keep every pair.
+    Allow,
+    /// Keep the first pair with the key.
+    First,
+    /// Keep the value of the last pair, at the position of the first.
+    Last,
+    /// Fail on the second pair with the key.
+    Reject,
+}
+
+/// Decodes a key or value, borrowing `input` when it is valid UTF-8 with
+/// nothing to decode.
+pub fn decode(input: &[u8]) -> Cow<'_, str> {
+    // Lossy decoding keeps malformed escapes, so it cannot fail.
+    match uri::decode(input, true, true).unwrap() {
+        Cow::Borrowed(bytes) => String::from_utf8_lossy(bytes),
+        Cow::Owned(bytes) => Cow::Owned(String::from_utf8_lossy(&bytes).into_owned()),
+    }
+}
+
+/// Iterator over decoded `(key, value)` pairs.
+#[derive(Clone, Debug)]
+pub struct Pairs<'a> {
+    pairs: std::vec::IntoIter<RawPair<'a>>,
+}
+
+impl<'a> Iterator for Pairs<'a> {
+    type Item = (Cow<'a, str>, Cow<'a, str>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pairs
+            .next()
+            .map(|(key, value)| (decode(key), decode(value)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.pairs.size_hint()
+    }
+}
+
+impl<'a> ExactSizeIterator for Pairs<'a> {}
+
+/// Splits `&` separated `key=value` pairs, skipping empty ones. A pair
+/// without `=` has an empty value.
+///
+/// Consumes the whole input. Going over the pair limit or a rejected
+/// duplicate key is a fatal error at the offending pair.
+pub struct FormParser {
+    pair: TakeTillAny,
+    separator: Byte,
+    max_pairs: usize,
+    duplicates: Duplicates,
+}
+
+impl FormParser {
+    pub fn new() -> Self {
+        Self {
+            pair: TakeTillAny::new(ByteSet::new(b"&")),
+            separator: Byte::new(b'&'),
+            max_pairs: 1000,
+            duplicates: Duplicates::Allow,
+        }
+    }
+
+    /// Most pairs accepted, 1000 by default.
+    pub fn max_pairs(mut self, max: usize) -> Self {
+        self.max_pairs = max;
+        self
+    }
+
+    pub fn duplicates(mut self, duplicates: Duplicates) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+
+    fn dedup<'a>(
+        &self,
+        input: &'a [u8],
+        pairs: Vec<RawPair<'a>>,
+    ) -> Result<Vec<RawPair<'a>>, ParseError> {
+        let mut seen: HashMap<Cow<'a, str>, usize> = HashMap::new();
+        let mut kept: Vec<RawPair<'a>> = Vec::with_capacity(pairs.len());
+
+        for (key, value) in pairs {
+            match (seen.entry(decode(key)), self.duplicates) {
+                (Entry::Vacant(entry), _) => {
+                    entry.insert(kept.len());
+                    kept.push((key, value));
+                }
+                (Entry::Occupied(_), Duplicates::First) => {}
+                (Entry::Occupied(entry), Duplicates::Last) => kept[*entry.get()].1 = value,
+                (Entry::Occupied(_), _) => {
+                    let offset = key.as_ptr() as usize - input.as_ptr() as usize;
+                    let expected = Expected::Token("unique key".into());
+                    return Err(ParseError::new(&input[offset..], expected).into_fatal());
+                }
+            }
+        }
+
+        Ok(kept)
+    }
+}
+
+impl Default for FormParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Parse<'a, &'a [u8]> for FormParser {
+    type Output = Pairs<'a>;
+
+    fn parse(&self, mut input: &'a [u8]) -> Result<(&'a [u8], Self::Output), ParseError> {
+        let start = input;
+        let mut pairs = Vec::new();
+
+        loop {
+            let (rest, pair) = self.pair.parse(input)?;
+
+            if !pair.is_empty() {
+                if pairs.len() == self.max_pairs {
+                    let expected = Expected::Token(format!("at most {} pairs", self.max_pairs));
+                    return Err(ParseError::new(input, expected).into_fatal());
+                }
+
+                pairs.push(match pair.iter().position(|&b| b == b'=') {
+                    Some(idx) => (&pair[..idx], &pair[idx + 1..]),
+                    None => (pair, &pair[pair.len()..]),
+                });
+            }
+
+            match self.separator.parse(rest) {
+                Ok((rest, _)) => input = rest,
+                Err(_) => {
+                    input = rest;
+                    break;
+                }
+            }
+        }
+
+        let pairs = match self.duplicates {
+            Duplicates::Allow => pairs,
+            _ => self.dedup(start, pairs)?,
+        };
+
+        Ok((
+            input,
+            Pairs {
+                pairs: pairs.into_iter(),
+            },
+        ))
+    }
+}
+
+#[inline]
+pub fn form<'a>() -> Cell<'a, FormParser> {
+    Cell::new(FormParser::new())
+}
@@ -0,0 +1,417 @@
+use super::{Head, Header, Scanner};
+use crate::cell::Cell;
+use crate::error::{Expected, Needed, ParseError};
+use crate::parser::Parse;
+use std::convert::TryFrom;
+
+/// Chunk size line, the extensions are validated but kept unparsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChunkHeader<'a> {
+    pub size: u64,
+    /// Everything after the size up to the line ending, starting at the
+    /// first `;` if there are any extensions.
+    pub extensions: &'a [u8],
+}
+
+/// Parses `chunk-size [ chunk-ext ] CRLF`.
+#[derive(Clone, Debug)]
+pub struct ChunkSize {
+    head: Head,
+}
+
+impl ChunkSize {
+    pub fn new() -> Self {
+        let mut head = Head::new();
+        head.max_head_size = 4096;
+        Self { head }
+    }
+
+    /// Longest chunk size line including extensions, in bytes.
+    pub fn max_line_size(mut self, max: usize) -> Self {
+        self.head.max_head_size = max;
+        self
+    }
+
+    /// Accept a bare LF as the line ending.
+    pub fn allow_bare_lf(mut self, allow: bool) -> Self {
+        self.head.allow_bare_lf = allow;
+        self
+    }
+}
+
+impl Default for ChunkSize {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Parse<'a, &'a [u8]> for ChunkSize {
+    type Output = ChunkHeader<'a>;
+
+    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), ParseError> {
+        let scanner = self.head.scanner(input);
+
+        // Running out of input is checked against the limit by the scanner,
+        // errors in a complete line are checked here.
+        match self.line(&scanner, input) {
+            Err(err) if err.offset(input) > self.head.max_head_size => Err(scanner.too_large()),
+            res => res,
+        }
+    }
+}
+
+impl ChunkSize {
+    fn line<'a>(
+        &self,
+        scanner: &Scanner<'_, 'a>,
+        input: &'a [u8],
+    ) -> Result<(&'a [u8], ChunkHeader<'a>), ParseError> {
+        let digits = input.iter().take_while(|b| b.is_ascii_hexdigit()).count();
+        if digits == input.len() {
+            return Err(scanner.incomplete(&input[digits..], Expected::Token("hex digit".into())));
+        }
+        if digits == 0 {
+            return Err(ParseError::new(input, Expected::Token("hex digit".into())));
+        }
+
+        let mut size: u64 = 0;
+        for (idx, digit) in input[..digits].iter().enumerate() {
+            let value = u64::from((*digit as char).to_digit(16).unwrap());
+            size = match size
+                .checked_mul(16)
+                .and_then(|size| size.checked_add(value))
+            {
+                Some(size) => size,
+                None => {
+                    let expected = Expected::Token("chunk size of at most 64 bits".into());
+                    return Err(ParseError::new(&input[idx..], expected).into_fatal());
+                }
+            };
+        }
+
+        let start = &input[digits..];
+        let mut rest = start;
+
+        loop {
+            let next = skip_whitespace(rest);
+            match next.first() {
+                Some(b';') => {}
+                Some(_) => break,
+                None => return Err(scanner.incomplete(next, Expected::Byte(b';'))),
+            }
+
+            let (next, _) = scanner.token(skip_whitespace(&next[1..]))?;
+            rest = next;
+
+            let next = skip_whitespace(rest);
+            match next.first() {
+                Some(b'=') => {}
+                Some(_) => continue,
+                None => return Err(scanner.incomplete(next, Expected::Byte(b'='))),
+            }
+
+            let next = skip_whitespace(&next[1..]);
+            rest = match next.first() {
+                Some(b'"') => quoted_string(scanner, next)?,
+                _ => scanner.token(next)?.0,
+            };
+        }
+
+        scanner.check_size(rest)?;
+
+        let extensions = &start[..start.len() - rest.len()];
+        let rest = scanner.newline(rest)?;
+
+        Ok((rest, ChunkHeader { size, extensions }))
+    }
+}
+
+fn skip_whitespace(input: &[u8]) -> &[u8] {
+    let len = input
+        .iter()
+        .take_while(|&&b| b == b' ' || b == b'\t')
+        .count();
+    &input[len..]
+}
+
+fn quoted_string<'a>(
+    scanner: &Scanner<'_, 'a>,
+    input: &'a [u8],
+) -> Result<&'a [u8], ParseError> {
+    let mut idx = 1;
+
+    loop {
+        match input.get(idx) {
+            Some(b'"') => return Ok(&input[idx + 1..]),
+            Some(b'\\') => match input.get(idx + 1) {
+                Some(b'\t') | Some(b' '..=b'~') | Some(0x80..=0xff) => idx += 2,
+                Some(_) => {
+                    let expected = Expected::Token("quoted character".into());
+                    return Err(ParseError::new(&input[idx + 1..], expected));
+                }
+                None => {
+                    let expected = Expected::Token("quoted character".into());
+                    return Err(scanner.incomplete(&input[idx + 1..], expected));
+                }
+            },
+            Some(b'\t') | Some(b' '..=b'~') | Some(0x80..=0xff) => idx += 1,
+            Some(_) => return Err(ParseError::new(&input[idx..], Expected::Byte(b'"'))),
+            None => return Err(scanner.incomplete(&input[idx..], Expected::Byte(b'"'))),
+        }
+    }
+}
+
+/// Takes up to `remaining` bytes of chunk data, whatever part of them is in
+/// the buffer.
+#[derive(Clone, Copy, Debug)]
+pub struct ChunkData {
+    remaining: u64,
+}
+
+impl ChunkData {
+    pub fn new(remaining: u64) -> Self {
+        Self { remaining }
+    }
+}
+
+impl<'a> Parse<'a, &'a [u8]> for ChunkData {
+    type Output = &'a [u8];
+
+    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), ParseError> {
+        if self.remaining == 0 {
+            return Ok((input, &input[..0]));
+        }
+
+        if input.is_empty() {
+            let needed = usize::try_from(self.remaining).map_or(Needed::Unknown, Needed::Size);
+            let expected = Expected::Token("chunk data".into());
+            return Err(ParseError::incomplete(input, needed, expected));
+        }
+
+        let len = usize::try_from(self.remaining).map_or(input.len(), |len| len.min(input.len()));
+        let (data, rest) = input.split_at(len);
+
+        Ok((rest, data))
+    }
+}
+
+/// Parses the trailer section after the last chunk, up to and including the
+/// empty line ending the body.
+#[derive(Clone, Debug)]
+pub struct Trailers {
+    head: Head,
+}
+
+impl Trailers {
+    pub fn new() -> Self {
+        Self { head: Head::new() }
+    }
+
+    pub fn max_fields(mut self, max: usize) -> Self {
+        self.head.max_headers = max;
+        self
+    }
+
+    /// Largest trailer section, in bytes.
+    pub fn max_size(mut self, max: usize) -> Self {
+        self.head.max_head_size = max;
+        self
+    }
+
+    /// Accept a bare LF wherever CRLF is expected.
+    pub fn allow_bare_lf(mut self, allow: bool) -> Self {
+        self.head.allow_bare_lf = allow;
+        self
+    }
+}
+
+impl Default for Trailers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Parse<'a, &'a [u8]> for Trailers {
+    type Output = Vec<Header<'a>>;
+
+    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), ParseError> {
+        self.head.scanner(input).headers(input)
+    }
+}
+
+/// Item decoded from a chunked body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Chunk<'a, D = &'a [u8]> {
+    /// Body data, a chunk may be split across several items.
+    Data(D),
+    /// End of the body with its trailer fields.
+    End(Vec<Header<'a>>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Phase {
+    Size,
+    Data(u64),
+    DataEnd,
+    Trailers,
+    Done,
+}
+
+/// Decodes a `Transfer-Encoding: chunked` body one buffer at a time.
+///
+/// The decoder remembers where in the body it is, so each buffer only has to
+/// start where the previous call stopped. Incomplete errors leave the state
+/// untouched, call again with the same bytes and more input after them.
+#[derive(Clone, Debug)]
+pub struct ChunkedDecoder {
+    size: ChunkSize,
+    trailers: Trailers,
+    phase: Phase,
+}
+
+impl ChunkedDecoder {
+    pub fn new() -> Self {
+        Self {
+            size: ChunkSize::new(),
+            trailers: Trailers::new(),
+            phase: Phase::Size,
+        }
+    }
+
+    /// Longest chunk size line including extensions, in bytes.
+    pub fn max_line_size(mut self, max: usize) -> Self {
+        self.size = self.size.max_line_size(max);
+        self
+    }
+
+    pub fn max_trailers(mut self, max: usize) -> Self {
+        self.trailers = self.trailers.max_fields(max);
+        self
+    }
+
+    /// Largest trailer section, in bytes.
+    pub fn max_trailer_size(mut self, max: usize) -> Self {
+        self.trailers = self.trailers.max_size(max);
+        self
+    }
+
+    /// Accept a bare LF wherever CRLF is expected.
+    pub fn allow_bare_lf(mut self, allow: bool) -> Self {
+        self.size = self.size.allow_bare_lf(allow);
+        self.trailers = self.trailers.allow_bare_lf(allow);
+        self
+    }
+
+    /// Whether the whole body including trailers has been decoded.
+    pub fn is_done(&self) -> bool {
+        self.phase == Phase::Done
+    }
+
+    /// Decodes the next item, borrowing data from `input`.
+    pub fn decode<'a>(&mut self, input: &'a [u8]) -> Result<(&'a [u8], Chunk<'a>), ParseError> {
+        self.next(input, usize::MAX, true)
+    }
+
+    /// Decodes data into `buf` until it is full, the input runs out or the
+    /// body ends, returning the number of bytes written.
+    ///
+    /// Fails with an incomplete error only when nothing could be written.
+    pub fn decode_into<'a>(
+        &mut self,
+        mut input: &'a [u8],
+        buf: &mut [u8],
+    ) -> Result<(&'a [u8], Chunk<'a, usize>), ParseError> {
+        let mut written = 0;
+
+        while written < buf.len() {
+            match self.next(input, buf.len() - written, written == 0) {
+                Ok((rest, Chunk::Data([]))) => {
+                    input = rest;
+                    break;
+                }
+                Ok((rest, Chunk::Data(data))) => {
+                    buf[written..written + data.len()].copy_from_slice(data);
+                    written += data.len();
+                    input = rest;
+                }
+                Ok((rest, Chunk::End(trailers))) => return Ok((rest, Chunk::End(trailers))),
+                Err(err) if err.is_incomplete() && written > 0 => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok((input, Chunk::Data(written)))
+    }
+
+    /// Decodes the next item with at most `limit` bytes of data, only
+    /// updating the state on success. Without `trailers` it stops in front of
+    /// the trailer section and returns empty data.
+    fn next<'a>(
+        &mut self,
+        mut input: &'a [u8],
+        limit: usize,
+        trailers: bool,
+    ) -> Result<(&'a [u8], Chunk<'a>), ParseError> {
+        let mut phase = self.phase;
+
+        loop {
+            match phase {
+                Phase::Size => {
+                    let (rest, header) = self.size.parse(input)?;
+                    input = rest;
+                    phase = match header.size {
+                        0 => Phase::Trailers,
+                        size => Phase::Data(size),
+                    };
+                }
+                Phase::Data(remaining) => {
+                    let clamped = remaining.min(limit as u64);
+                    let (rest, data) = ChunkData::new(clamped).parse(input)?;
+                    self.phase = match remaining - data.len() as u64 {
+                        0 => Phase::DataEnd,
+                        remaining => Phase::Data(remaining),
+                    };
+                    return Ok((rest, Chunk::Data(data)));
+                }
+                Phase::DataEnd => {
+                    input = self.size.head.scanner(input).newline(input)?;
+                    phase = Phase::Size;
+                }
+                Phase::Trailers if !trailers => {
+                    self.phase = Phase::Trailers;
+                    return Ok((input, Chunk::Data(&input[..0])));
+                }
+                Phase::Trailers => {
+                    let (rest, trailers) = self.trailers.parse(input)?;
+                    self.phase = Phase::Done;
+                    return Ok((rest, Chunk::End(trailers)));
+                }
+                Phase::Done => {
+                    let expected = Expected::Token("end of chunked body".into());
+                    return Err(ParseError::new(input, expected));
+                }
+            }
+        }
+    }
+}
+
+impl Default for ChunkedDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[inline]
+pub fn chunk_size<'a>() -> Cell<'a, ChunkSize> {
+    Cell::new(ChunkSize::new())
+}
+
+#[inline]
+pub fn chunk_data<'a>(remaining: u64) -> Cell<'a, ChunkData> {
+    Cell::new(ChunkData::new(remaining))
+}
+
+#[inline]
+pub fn trailers<'a>() -> Cell<'a, Trailers> {
+    Cell::new(Trailers::new())
+}
@@ -0,0 +1,264 @@
+//! HTTP/1.1 message parsing following RFC 9112.
+//!
+//! The parsers borrow everything from the input buffer. When the buffer ends
+//! before the message head does they return an incomplete error, so a server
+//! can read more bytes and parse again, and limit violations are fatal.
+
+mod chunked;
+pub mod multipart;
+mod request;
+mod response;
+
+pub use self::chunked::{
+    chunk_data, chunk_size, trailers, Chunk, ChunkData, ChunkHeader, ChunkSize, ChunkedDecoder,
+    Trailers,
+};
+pub use self::request::{request, Method, Request, RequestParser};
+pub use self::response::{response, Response, ResponseParser};
+
+use crate::error::{Expected, Needed, ParseError};
+use crate::simd::{self, ByteSet};
+
+const SP: u8 = b' ';
+const HTAB: u8 = b'\t';
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Version {
+    Http10,
+    Http11,
+}
+
+/// Header field, the value has surrounding whitespace removed.
+///
+/// Values may contain obs-text, so they are kept as bytes. With obs-fold
+/// allowed a folded value still contains the line breaks of the fold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header<'a> {
+    pub name: &'a str,
+    pub value: &'a [u8],
+}
+
+impl<'a> Header<'a> {
+    pub fn value_str(&self) -> Option<&'a str> {
+        std::str::from_utf8(self.value).ok()
+    }
+}
+
+/// Finds the first header named `name`, ignoring ASCII case.
+pub fn find_header<'h, 'a>(headers: &'h [Header<'a>], name: &str) -> Option<&'h Header<'a>> {
+    headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case(name))
+}
+
+/// Grammar and limits shared by the request and response parsers.
+#[derive(Clone, Debug)]
+pub(crate) struct Head {
+    pub(crate) max_headers: usize,
+    pub(crate) max_head_size: usize,
+    pub(crate) allow_bare_lf: bool,
+    pub(crate) allow_obs_fold: bool,
+    tchar: ByteSet,
+    field: ByteSet,
+    vchar: ByteSet,
+}
+
+impl Head {
+    pub(crate) fn new() -> Self {
+        Self {
+            max_headers: 100,
+            max_head_size: 8192,
+            allow_bare_lf: false,
+            allow_obs_fold: false,
+            tchar: ByteSet::from_ranges(&[
+                b'!'..=b'!',
+                b'#'..=b'\'',
+                b'*'..=b'+',
+                b'-'..=b'.',
+                b'0'..=b'9',
+                b'A'..=b'Z',
+                b'^'..=b'z',
+                b'|'..=b'|',
+                b'~'..=b'~',
+            ]),
+            field: ByteSet::from_ranges(&[HTAB..=HTAB, SP..=b'~', 0x80..=0xff]),
+            vchar: ByteSet::from_ranges(&[b'!'..=b'~']),
+        }
+    }
+
+    /// Starts parsing a message head that begins at `start`.
+    pub(crate) fn scanner<'p, 'a>(&'p self, start: &'a [u8]) -> Scanner<'p, 'a> {
+        Scanner { head: self, start }
+    }
+}
+
+/// Parses the pieces of one message head, keeping track of its start to
+/// enforce the size limit.
+pub(crate) struct Scanner<'p, 'a> {
+    head: &'p Head,
+    start: &'a [u8],
+}
+
+impl<'p, 'a> Scanner<'p, 'a> {
+    /// Error for running out of input, fatal once the head is over the size
+    /// limit since more input cannot help.
+    pub(crate) fn incomplete(&self, input: &'a [u8], expected: Expected) -> ParseError {
+        if self.start.len() >= self.head.max_head_size {
+            return self.too_large();
+        }
+
+        ParseError::incomplete(input, Needed::Unknown, expected)
+    }
+
+    pub(crate) fn too_large(&self) -> ParseError {
+        let max = self.head.max_head_size;
+        let at = &self.start[max.min(self.start.len())..];
+
+        ParseError::new(at, Expected::Token(format!("message head of at most {} bytes", max)))
+            .into_fatal()
+    }
+
+    /// Fails once the consumed part of the head is over the size limit.
+    pub(crate) fn check_size(&self, input: &'a [u8]) -> Result<(), ParseError> {
+        if self.start.len() - input.len() > self.head.max_head_size {
+            return Err(self.too_large());
+        }
+
+        Ok(())
+    }
+
+    /// Non-empty run of bytes in `set`, which must be followed by more input.
+    fn run(
+        &self,
+        input: &'a [u8],
+        set: &ByteSet,
+        expected: fn() -> Expected,
+    ) -> Result<(&'a [u8], &'a str), ParseError> {
+        match simd::find_not(input, set) {
+            Some(0) => Err(ParseError::new(input, expected())),
+            Some(len) => {
+                let (run, rest) = input.split_at(len);
+                // Every set passed in here is ASCII only.
+                Ok((rest, std::str::from_utf8(run).unwrap()))
+            }
+            None => Err(self.incomplete(&input[input.len()..], expected())),
+        }
+    }
+
+    pub(crate) fn token(&self, input: &'a [u8]) -> Result<(&'a [u8], &'a str), ParseError> {
+        self.run(input, &self.head.tchar, || Expected::Token("token".into()))
+    }
+
+    /// Visible ASCII, as used by the request target.
+    pub(crate) fn vchars(&self, input: &'a [u8]) -> Result<(&'a [u8], &'a str), ParseError> {
+        self.run(input, &self.head.vchar, || {
+            Expected::Token("visible character".into())
+        })
+    }
+
+    pub(crate) fn byte(&self, input: &'a [u8], byte: u8) -> Result<&'a [u8], ParseError> {
+        match input.split_first() {
+            Some((&first, rest)) if first == byte => Ok(rest),
+            Some(_) => Err(ParseError::new(input, Expected::Byte(byte))),
+            None => Err(self.incomplete(input, Expected::Byte(byte))),
+        }
+    }
+
+    /// CRLF, or a bare LF when allowed.
+    pub(crate) fn newline(&self, input: &'a [u8]) -> Result<&'a [u8], ParseError> {
+        let expected = || Expected::Slice(b"\r\n".to_vec().into_boxed_slice());
+
+        match input {
+            [b'\r', b'\n', rest @ ..] => Ok(rest),
+            [b'\n', rest @ ..] if self.head.allow_bare_lf => Ok(rest),
+            [] | [b'\r'] => Err(self.incomplete(&input[input.len()..], expected())),
+            _ => Err(ParseError::new(input, expected())),
+        }
+    }
+
+    /// `HTTP/1.0` or `HTTP/1.1`.
+    pub(crate) fn version(&self, input: &'a [u8]) -> Result<(&'a [u8], Version), ParseError> {
+        let expected = |version: &[u8]| Expected::Slice(version.to_vec().into_boxed_slice());
+
+        for &(name, version) in &[(b"HTTP/1.1", Version::Http11), (b"HTTP/1.0", Version::Http10)] {
+            if input.starts_with(name) {
+                return Ok((&input[name.len()..], version));
+            }
+        }
+
+        if input.len() < 8 && b"HTTP/1.".starts_with(&input[..input.len().min(7)]) {
+            return Err(self.incomplete(&input[input.len()..], expected(b"HTTP/1.1")));
+        }
+
+        Err(ParseError::new(input, expected(b"HTTP/1.1"))
+            .merge(ParseError::new(input, expected(b"HTTP/1.0"))))
+    }
+
+    /// Header fields up to and including the empty line ending the head.
+    pub(crate) fn headers(
+        &self,
+        mut input: &'a [u8],
+    ) -> Result<(&'a [u8], Vec<Header<'a>>), ParseError> {
+        let mut headers = Vec::new();
+
+        loop {
+            self.check_size(input)?;
+
+            match self.newline(input) {
+                Ok(rest) => return Ok((rest, headers)),
+                Err(err) if err.is_incomplete() => return Err(err),
+                Err(_) => {}
+            }
+
+            if headers.len() == self.head.max_headers {
+                let max = self.head.max_headers;
+                let expected = Expected::Token(format!("at most {} header fields", max));
+                return Err(ParseError::new(input, expected).into_fatal());
+            }
+
+            let (rest, name) = self.token(input)?;
+            let rest = self.byte(rest, b':')?;
+            let (rest, value) = self.field_value(rest)?;
+
+            headers.push(Header { name, value });
+            input = rest;
+        }
+    }
+
+    /// Field value with optional whitespace and the line ending removed.
+    fn field_value(&self, input: &'a [u8]) -> Result<(&'a [u8], &'a [u8]), ParseError> {
+        let start = input
+            .iter()
+            .position(|&b| b != SP && b != HTAB)
+            .unwrap_or(input.len());
+        let mut end = start;
+
+        loop {
+            end += simd::find_not(&input[end..], &self.head.field).unwrap_or(input.len() - end);
+            let rest = self.newline(&input[end..])?;
+
+            match rest.first() {
+                None => {
+                    let expected = Expected::Token("field line".into());
+                    return Err(self.incomplete(rest, expected));
+                }
+                Some(&SP) | Some(&HTAB) if self.head.allow_obs_fold => {
+                    end = input.len() - rest.len();
+                }
+                Some(&SP) | Some(&HTAB) => {
+                    let expected = Expected::Token("field line without obs-fold".into());
+                    return Err(ParseError::new(rest, expected));
+                }
+                Some(_) => {
+                    let value = &input[start..end];
+                    let trimmed = value
+                        .iter()
+                        .rposition(|&b| b != SP && b != HTAB)
+                        .map_or(0, |idx| idx + 1);
+
+                    return Ok((rest, &value[..trimmed]));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,430 @@
+//! `multipart/form-data` bodies, RFC 7578.
+
+use super::{find_header, Head, Header};
+use crate::error::{Expected, Needed, ParseError};
+use crate::parser::Parse;
+use crate::streaming::simd::TakeUntilLiteral;
+use std::borrow::Cow;
+
+/// Boundary parameter of a `multipart/*` content type, without quotes.
+pub fn boundary(content_type: &[u8]) -> Option<&[u8]> {
+    let (media_type, params) = parameters(content_type);
+
+    if !media_type.to_ascii_lowercase().starts_with(b"multipart/") {
+        return None;
+    }
+
+    params
+        .into_iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(b"boundary"))
+        .and_then(|(_, value)| match value {
+            Cow::Borrowed(value) if !value.is_empty() => Some(value),
+            _ => None,
+        })
+}
+
+type Parameters<'a> = Vec<(&'a [u8], Cow<'a, [u8]>)>;
+
+/// Splits `type; name=value; name="quoted value"`, unescaping quoted values.
+fn parameters(value: &[u8]) -> (&[u8], Parameters<'_>) {
+    let trim = |bytes: &[u8]| -> usize {
+        bytes
+            .iter()
+            .take_while(|&&b| b == b' ' || b == b'\t')
+            .count()
+    };
+    let end = value.iter().position(|&b| b == b';').unwrap_or(value.len());
+    let media_type = value[..end].trim_ascii();
+    let mut params = Vec::new();
+    let mut rest = &value[end..];
+
+    while let Some(next) = rest.strip_prefix(b";") {
+        let next = &next[trim(next)..];
+        let len = next
+            .iter()
+            .position(|&b| b == b'=' || b == b';')
+            .unwrap_or(next.len());
+        let name = next[..len].trim_ascii();
+        rest = &next[len..];
+
+        let value = match rest.strip_prefix(b"=") {
+            Some(next) => {
+                let next = &next[trim(next)..];
+                let (value, len) = match next.first() {
+                    Some(b'"') => quoted(next),
+                    _ => {
+                        let len = next.iter().position(|&b| b == b';').unwrap_or(next.len());
+                        (Cow::Borrowed(next[..len].trim_ascii()), len)
+                    }
+                };
+                rest = &next[len..];
+                rest = &rest[rest.iter().position(|&b| b == b';').unwrap_or(rest.len())..];
+                value
+            }
+            None => Cow::Borrowed(&rest[..0]),
+        };
+
+        if !name.is_empty() {
+            params.push((name, value));
+        }
+    }
+
+    (media_type, params)
+}
+
+/// Quoted string at the start of `input` and the length it takes up. An
+/// unterminated string runs to the end.
+fn quoted(input: &[u8]) -> (Cow<'_, [u8]>, usize) {
+    let body = &input[1..];
+    let end = body.iter().position(|&b| b == b'"' || b == b'\\');
+
+    match end {
+        Some(end) if body[end] == b'"' => return (Cow::Borrowed(&body[..end]), end + 2),
+        None => return (Cow::Borrowed(body), input.len()),
+        Some(_) => {}
+    }
+
+    let mut value = Vec::new();
+    let mut idx = 0;
+
+    while idx < body.len() {
+        match body[idx] {
+            b'"' => return (Cow::Owned(value), idx + 2),
+            b'\\' if idx + 1 < body.len() => {
+                value.push(body[idx + 1]);
+                idx += 2;
+            }
+            b => {
+                value.push(b);
+                idx += 1;
+            }
+        }
+    }
+
+    (Cow::Owned(value), input.len())
+}
+
+fn lossy(value: Cow<'_, [u8]>) -> Cow<'_, str> {
+    match value {
+        Cow::Borrowed(value) => String::from_utf8_lossy(value),
+        Cow::Owned(value) => Cow::Owned(String::from_utf8_lossy(&value).into_owned()),
+    }
+}
+
+/// Parsed `Content-Disposition` header of a part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Disposition<'a> {
+    /// Disposition type, `form-data` for form fields.
+    pub kind: &'a str,
+    pub name: Option<Cow<'a, str>>,
+    pub filename: Option<Cow<'a, str>>,
+}
+
+impl<'a> Disposition<'a> {
+    pub fn from_headers(headers: &[Header<'a>]) -> Option<Self> {
+        let value = find_header(headers, "content-disposition")?.value;
+        let (kind, params) = parameters(value);
+        let mut disposition = Disposition {
+            kind: std::str::from_utf8(kind).ok()?,
+            name: None,
+            filename: None,
+        };
+
+        for (name, value) in params {
+            if name.eq_ignore_ascii_case(b"name") && disposition.name.is_none() {
+                disposition.name = Some(lossy(value));
+            } else if name.eq_ignore_ascii_case(b"filename") && disposition.filename.is_none() {
+                disposition.filename = Some(lossy(value));
+            }
+        }
+
+        Some(disposition)
+    }
+}
+
+/// Part of a multipart body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Part<'a> {
+    pub headers: Vec<Header<'a>>,
+    pub body: &'a [u8],
+}
+
+impl<'a> Part<'a> {
+    /// Value of the first header named `name`, ignoring ASCII case.
+    pub fn header(&self, name: &str) -> Option<&'a [u8]> {
+        find_header(&self.headers, name).map(|header| header.value)
+    }
+
+    pub fn disposition(&self) -> Option<Disposition<'a>> {
+        Disposition::from_headers(&self.headers)
+    }
+
+    /// Form field name from the `Content-Disposition` header.
+    pub fn name(&self) -> Option<Cow<'a, str>> {
+        self.disposition()?.name
+    }
+
+    pub fn filename(&self) -> Option<Cow<'a, str>> {
+        self.disposition()?.filename
+    }
+
+    pub fn content_type(&self) -> Option<&'a [u8]> {
+        self.header("content-type")
+    }
+}
+
+/// Item decoded from a multipart body.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Event<'a> {
+    /// Header fields starting a new part.
+    Headers(Vec<Header<'a>>),
+    /// Body data of the current part, a body may be split across several
+    /// items and an empty body has none.
+    Data(&'a [u8]),
+    /// Close delimiter, the rest of the input is the epilogue.
+    End,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Phase {
+    Preamble,
+    Delimiter,
+    Headers,
+    Body,
+    Done,
+}
+
+/// Parses a complete `multipart/form-data` body into its parts, leaving the
+/// epilogue unconsumed. Use [`MultipartDecoder`] to avoid buffering the
+/// whole body.
+pub struct MultipartParser {
+    dash_boundary: Box<[u8]>,
+    delimiter: TakeUntilLiteral,
+    delimiter_len: usize,
+    head: Head,
+}
+
+impl MultipartParser {
+    pub fn new(boundary: &[u8]) -> Self {
+        let dash_boundary = [&b"--"[..], boundary].concat();
+        let delimiter = [&b"\r\n"[..], &dash_boundary].concat();
+
+        Self {
+            delimiter: TakeUntilLiteral::new(&delimiter),
+            delimiter_len: delimiter.len(),
+            dash_boundary: dash_boundary.into_boxed_slice(),
+            head: Head::new(),
+        }
+    }
+
+    /// Most header fields in a part.
+    pub fn max_headers(mut self, max: usize) -> Self {
+        self.head.max_headers = max;
+        self
+    }
+
+    /// Largest header section of a part, in bytes.
+    pub fn max_head_size(mut self, max: usize) -> Self {
+        self.head.max_head_size = max;
+        self
+    }
+
+    /// Accept a bare LF after delimiters and in part headers. Delimiters
+    /// inside the body still need CRLF.
+    pub fn allow_bare_lf(mut self, allow: bool) -> Self {
+        self.head.allow_bare_lf = allow;
+        self
+    }
+
+    /// Decodes the next event, only updating `state` on success.
+    fn step<'a>(
+        &self,
+        state: &mut Phase,
+        mut input: &'a [u8],
+    ) -> Result<(&'a [u8], Event<'a>), ParseError> {
+        let mut phase = *state;
+
+        loop {
+            let scanner = self.head.scanner(input);
+
+            match phase {
+                Phase::Preamble => {
+                    let dash = &self.dash_boundary[..];
+                    if input.len() < dash.len() && dash.starts_with(input) {
+                        return Err(scanner.incomplete(input, Expected::Slice(dash.into())));
+                    }
+
+                    let mut rest = match input.strip_prefix(dash) {
+                        Some(rest) => rest,
+                        None => {
+                            let (rest, _) = self.delimiter.parse(input)?;
+                            &rest[self.delimiter_len..]
+                        }
+                    };
+
+                    // A preamble line that only starts with the boundary is
+                    // not a delimiter, look for the next one.
+                    loop {
+                        match self.ends_delimiter(rest) {
+                            Some(true) => break,
+                            Some(false) => {
+                                let (next, _) = self.delimiter.parse(rest)?;
+                                rest = &next[self.delimiter_len..];
+                            }
+                            None => {
+                                let expected = Expected::Slice(b"\r\n"[..].into());
+                                let end = &rest[rest.len()..];
+                                return Err(ParseError::incomplete(end, Needed::Unknown, expected));
+                            }
+                        }
+                    }
+
+                    input = rest;
+                    phase = Phase::Delimiter;
+                }
+                Phase::Delimiter => {
+                    match input {
+                        [b'-', b'-', rest @ ..] => {
+                            *state = Phase::Done;
+                            return Ok((rest, Event::End));
+                        }
+                        [b'-'] => {
+                            return Err(scanner.incomplete(input, Expected::Slice(b"--"[..].into())))
+                        }
+                        _ => {}
+                    }
+
+                    let padding = input
+                        .iter()
+                        .take_while(|&&b| b == b' ' || b == b'\t')
+                        .count();
+                    input = scanner.newline(&input[padding..])?;
+                    phase = Phase::Headers;
+                }
+                Phase::Headers => {
+                    let (rest, headers) = scanner.headers(input)?;
+                    *state = Phase::Body;
+                    return Ok((rest, Event::Headers(headers)));
+                }
+                Phase::Body => match self.delimiter.parse(input) {
+                    Ok((rest, data)) => {
+                        input = &rest[self.delimiter_len..];
+                        phase = Phase::Delimiter;
+
+                        if !data.is_empty() {
+                            *state = phase;
+                            return Ok((input, Event::Data(data)));
+                        }
+                    }
+                    Err(err) if err.is_incomplete() => {
+                        // Hold back a tail that may be the start of the
+                        // delimiter.
+                        let from = input.len().saturating_sub(self.delimiter_len - 1);
+                        let safe = (from..input.len())
+                            .find(|&idx| self.is_delimiter_prefix(&input[idx..]))
+                            .unwrap_or(input.len());
+
+                        if safe == 0 {
+                            return Err(err);
+                        }
+
+                        *state = phase;
+                        return Ok((&input[safe..], Event::Data(&input[..safe])));
+                    }
+                    Err(err) => return Err(err),
+                },
+                Phase::Done => {
+                    let expected = Expected::Token("end of multipart body".into());
+                    return Err(ParseError::new(input, expected));
+                }
+            }
+        }
+    }
+
+    /// Whether `rest`, which follows a boundary, ends a delimiter line with
+    /// `--` or optional whitespace and a line ending. `None` when more input
+    /// is needed to tell.
+    fn ends_delimiter(&self, rest: &[u8]) -> Option<bool> {
+        let padding = rest
+            .iter()
+            .take_while(|&&b| b == b' ' || b == b'\t')
+            .count();
+
+        match (rest, &rest[padding..]) {
+            ([b'-', b'-', ..], _) => Some(true),
+            ([b'-'], _) => None,
+            (_, [b'\r', b'\n', ..]) => Some(true),
+            (_, [b'\n', ..]) => Some(self.head.allow_bare_lf),
+            (_, []) | (_, [b'\r']) => None,
+            _ => Some(false),
+        }
+    }
+
+    fn is_delimiter_prefix(&self, bytes: &[u8]) -> bool {
+        match bytes {
+            [] => false,
+            [b'\r'] => true,
+            [b'\r', b'\n', rest @ ..] => self.dash_boundary.starts_with(rest),
+            _ => false,
+        }
+    }
+}
+
+impl<'a> Parse<'a, &'a [u8]> for MultipartParser {
+    type Output = Vec<Part<'a>>;
+
+    fn parse(&self, mut input: &'a [u8]) -> Result<(&'a [u8], Self::Output), ParseError> {
+        let mut phase = Phase::Preamble;
+        let mut parts: Vec<Part<'a>> = Vec::new();
+
+        loop {
+            let (rest, event) = self.step(&mut phase, input)?;
+            input = rest;
+
+            match event {
+                Event::Headers(headers) => parts.push(Part {
+                    headers,
+                    body: &input[..0],
+                }),
+                // With the whole body available each part has its data in
+                // one piece.
+                Event::Data(data) => parts.last_mut().unwrap().body = data,
+                Event::End => return Ok((input, parts)),
+            }
+        }
+    }
+}
+
+/// Decodes a multipart body one buffer at a time.
+///
+/// Part data is handed out as soon as it cannot be part of a delimiter, so
+/// only the preamble and the part headers need to be buffered whole.
+/// Incomplete errors leave the state untouched, call again with the same
+/// bytes and more input after them.
+pub struct MultipartDecoder {
+    parser: MultipartParser,
+    phase: Phase,
+}
+
+impl MultipartDecoder {
+    pub fn new(boundary: &[u8]) -> Self {
+        Self::with_parser(MultipartParser::new(boundary))
+    }
+
+    /// Decoder using the limits of `parser`.
+    pub fn with_parser(parser: MultipartParser) -> Self {
+        Self {
+            parser,
+            phase: Phase::Preamble,
+        }
+    }
+
+    /// Whether the close delimiter has been reached.
+    pub fn is_done(&self) -> bool {
+        self.phase == Phase::Done
+    }
+
+    pub fn decode<'a>(&mut self, input: &'a [u8]) -> Result<(&'a [u8], Event<'a>), ParseError> {
+        self.parser.step(&mut self.phase, input)
+    }
+}
@@ -0,0 +1,141 @@
+use super::{Head, Header, Version};
+use crate::cell::Cell;
+use crate::error::ParseError;
+use crate::parser::Parse;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method<'a> {
+    Get,
+    Head,
+    Post,
+    Put,
+    Delete,
+    Connect,
+    Options,
+    Trace,
+    Patch,
+    /// Any other method token.
+    Other(&'a str),
+}
+
+impl<'a> Method<'a> {
+    pub fn new(token: &'a str) -> Self {
+        match token {
+            "GET" => Method::Get,
+            "HEAD" => Method::Head,
+            "POST" => Method::Post,
+            "PUT" => Method::Put,
+            "DELETE" => Method::Delete,
+            "CONNECT" => Method::Connect,
+            "OPTIONS" => Method::Options,
+            "TRACE" => Method::Trace,
+            "PATCH" => Method::Patch,
+            other => Method::Other(other),
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        match self {
+            Method::Get => "GET",
+            Method::Head => "HEAD",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+            Method::Connect => "CONNECT",
+            Method::Options => "OPTIONS",
+            Method::Trace => "TRACE",
+            Method::Patch => "PATCH",
+            Method::Other(other) => other,
+        }
+    }
+}
+
+/// Request line and header fields of an HTTP/1.x request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Request<'a> {
+    pub method: Method<'a>,
+    pub target: &'a str,
+    pub version: Version,
+    pub headers: Vec<Header<'a>>,
+}
+
+impl<'a> Request<'a> {
+    /// Value of the first header named `name`, ignoring ASCII case.
+    pub fn header(&self, name: &str) -> Option<&'a [u8]> {
+        super::find_header(&self.headers, name).map(|header| header.value)
+    }
+}
+
+/// Parses a request head, leaving the body unconsumed.
+///
+/// Strict by default: bare LF line endings and obs-fold are rejected, at
+/// most 100 header fields and 8 KiB of head are accepted.
+#[derive(Clone, Debug)]
+pub struct RequestParser {
+    head: Head,
+}
+
+impl RequestParser {
+    pub fn new() -> Self {
+        Self { head: Head::new() }
+    }
+
+    pub fn max_headers(mut self, max: usize) -> Self {
+        self.head.max_headers = max;
+        self
+    }
+
+    /// Largest request line plus header section, in bytes.
+    pub fn max_head_size(mut self, max: usize) -> Self {
+        self.head.max_head_size = max;
+        self
+    }
+
+    /// Accept a bare LF wherever CRLF is expected.
+    pub fn allow_bare_lf(mut self, allow: bool) -> Self {
+        self.head.allow_bare_lf = allow;
+        self
+    }
+
+    /// Accept header values continued on lines starting with whitespace.
+    pub fn allow_obs_fold(mut self, allow: bool) -> Self {
+        self.head.allow_obs_fold = allow;
+        self
+    }
+}
+
+impl Default for RequestParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Parse<'a, &'a [u8]> for RequestParser {
+    type Output = Request<'a>;
+
+    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), ParseError> {
+        let scanner = self.head.scanner(input);
+
+        let (rest, method) = scanner.token(input)?;
+        let rest = scanner.byte(rest, b' ')?;
+        let (rest, target) = scanner.vchars(rest)?;
+        let rest = scanner.byte(rest, b' ')?;
+        let (rest, version) = scanner.version(rest)?;
+        let rest = scanner.newline(rest)?;
+        let (rest, headers) = scanner.headers(rest)?;
+
+        let request = Request {
+            method: Method::new(method),
+            target,
+            version,
+            headers,
+        };
+
+        Ok((rest, request))
+    }
+}
+
+#[inline]
+pub fn request<'a>() -> Cell<'a, RequestParser> {
+    Cell::new(RequestParser::new())
+}
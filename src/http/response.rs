@@ -0,0 +1,281 @@
+use super::{Head, Header, Version, HTAB, SP};
+use crate::cell::Cell;
+use crate::error::{Expected, ParseError};
+use crate::parser::simd::TakeWhileSet;
+use crate::parser::{
+    And, Byte, Context, Count, Cut, Many0, ManyMN, Map, Opt, Or, Parse, Recognize, Satisfy, Skip,
+    SkipLeft, Slice, TakeUntil, Value, Verify,
+};
+use crate::simd::ByteSet;
+use crate::streaming;
+use std::fmt;
+use std::sync::Arc;
+
+/// Status line and header fields of an HTTP/1.x response.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response<'a> {
+    pub version: Version,
+    pub status: u16,
+    /// Reason phrase, empty when the status line has none. It may contain
+    /// obs-text so it is kept as bytes.
+    pub reason: &'a [u8],
+    pub headers: Vec<Header<'a>>,
+}
+
+impl<'a> Response<'a> {
+    pub fn reason_str(&self) -> Option<&'a str> {
+        std::str::from_utf8(self.reason).ok()
+    }
+
+    /// Value of the first header named `name`, ignoring ASCII case.
+    pub fn header(&self, name: &str) -> Option<&'a [u8]> {
+        super::find_header(&self.headers, name).map(|header| header.value)
+    }
+
+    /// Whether this is a 1xx response that is followed by another response
+    /// to the same request. `101 Switching Protocols` is not, the connection
+    /// stops speaking HTTP/1.1 after it.
+    pub fn is_interim(&self) -> bool {
+        (100..200).contains(&self.status) && self.status != 101
+    }
+}
+
+/// Parses a response head, leaving the body unconsumed.
+///
+/// Has the same defaults as [`RequestParser`](super::RequestParser). A status
+/// line without a reason phrase is accepted, with or without the space after
+/// the status code.
+///
+/// The head is only parsed once the empty line ending it is buffered, until
+/// then the error is incomplete unless the head is over its size limit.
+#[derive(Clone)]
+pub struct ResponseParser {
+    head: Head,
+    skip_interim: bool,
+    grammar: Grammar,
+}
+
+impl ResponseParser {
+    pub fn new() -> Self {
+        let head = Head::new();
+
+        Self {
+            grammar: Grammar::new(&head),
+            head,
+            skip_interim: false,
+        }
+    }
+
+    pub fn max_headers(mut self, max: usize) -> Self {
+        self.head.max_headers = max;
+        self.rebuild()
+    }
+
+    /// Largest status line plus header section, in bytes. Applies to each
+    /// response head separately.
+    pub fn max_head_size(mut self, max: usize) -> Self {
+        self.head.max_head_size = max;
+        self
+    }
+
+    /// Accept a bare LF wherever CRLF is expected.
+    pub fn allow_bare_lf(mut self, allow: bool) -> Self {
+        self.head.allow_bare_lf = allow;
+        self.rebuild()
+    }
+
+    /// Accept header values continued on lines starting with whitespace.
+    pub fn allow_obs_fold(mut self, allow: bool) -> Self {
+        self.head.allow_obs_fold = allow;
+        self.rebuild()
+    }
+
+    /// Consume interim 1xx responses and return the response after them.
+    pub fn skip_interim(mut self, skip: bool) -> Self {
+        self.skip_interim = skip;
+        self
+    }
+
+    fn rebuild(mut self) -> Self {
+        self.grammar = Grammar::new(&self.head);
+        self
+    }
+
+    fn parse_head<'a>(&self, input: &'a [u8]) -> Result<(&'a [u8], Response<'a>), ParseError> {
+        let head = &self.head;
+        let scanner = head.scanner(input);
+
+        match self.grammar.block.parse(input) {
+            Ok((_, block)) if block.len() > head.max_head_size => return Err(scanner.too_large()),
+            Ok(_) => {}
+            Err(_) if input.len() >= head.max_head_size => return Err(scanner.too_large()),
+            Err(err) => return Err(err),
+        }
+
+        let (rest, (((version, status), reason), headers)) = self.grammar.head.parse(input)?;
+
+        let rest = match self.grammar.newline.parse(rest) {
+            Ok((rest, _)) => rest,
+            Err(_) if headers.len() == head.max_headers => {
+                let max = head.max_headers;
+                let expected = Expected::Token(format!("at most {} header fields", max));
+                return Err(ParseError::new(rest, expected).into_fatal());
+            }
+            Err(err) => return Err(err),
+        };
+
+        let response = Response {
+            version,
+            status,
+            reason,
+            headers,
+        };
+
+        Ok((rest, response))
+    }
+}
+
+impl fmt::Debug for ResponseParser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseParser")
+            .field("head", &self.head)
+            .field("skip_interim", &self.skip_interim)
+            .finish_non_exhaustive()
+    }
+}
+
+type StatusLine<'a> = ((Version, u16), &'a [u8]);
+type SliceParser = Arc<dyn for<'a> Parse<'a, &'a [u8], Output = &'a [u8]> + Send + Sync>;
+type HeadParser =
+    Arc<dyn for<'a> Parse<'a, &'a [u8], Output = (StatusLine<'a>, Vec<Header<'a>>)> + Send + Sync>;
+
+/// Combinators for a response head, built once for the options of a
+/// [`ResponseParser`] and shared by its clones.
+#[derive(Clone)]
+struct Grammar {
+    /// Everything up to and including the empty line ending the head.
+    block: SliceParser,
+    /// Status line and header fields, without the empty line.
+    head: HeadParser,
+    newline: SliceParser,
+}
+
+impl Grammar {
+    /// Assembled from the parser types rather than cells, since a cell only
+    /// parses inputs of the lifetime it was created for.
+    fn new(head: &Head) -> Self {
+        // The head grammar is not streaming, so the empty line ending the
+        // head has to be buffered before it runs. Bare LFs are allowed here
+        // and rejected by the grammar, which reports them where they are.
+        let blank_line = || {
+            let cr = Opt::new(streaming::Byte::new(b'\r'));
+            And::new(And::new(streaming::Byte::new(b'\n'), cr), streaming::Byte::new(b'\n'))
+        };
+        let block = Recognize::new(And::new(TakeUntil::new(blank_line()), blank_line()));
+
+        let allow_bare_lf = head.allow_bare_lf;
+        let allow_obs_fold = head.allow_obs_fold;
+        let newline = || {
+            let lf = Verify::new(Slice::new(b"\n"), move |_: &&[u8]| allow_bare_lf);
+            Or::new(Slice::new(b"\r\n"), lf)
+        };
+        let ows = || TakeWhileSet::new(ByteSet::new(&[SP, HTAB]));
+        // Field text ends at CR or LF, any other byte outside of it is left
+        // for the newline after it to report.
+        let text = || {
+            let field = head.field.clone();
+            TakeUntil::new(Satisfy::new(move |b: &u8| !field.contains(*b)))
+        };
+
+        let version = Or::new(
+            Value::new(Slice::new(b"HTTP/1.1"), Version::Http11),
+            Value::new(Slice::new(b"HTTP/1.0"), Version::Http10),
+        );
+        let digits = Count::new(Satisfy::new(u8::is_ascii_digit), 3);
+        let status = Context::new(Map::new(digits, status_code), "status code");
+        let reason = Map::new(Opt::new(SkipLeft::new(Byte::new(b' '), text())), or_empty);
+        let status_line = And::new(
+            And::new(Skip::new(version, Byte::new(b' ')), status),
+            Skip::new(reason, newline()),
+        );
+
+        let name = Verify::new(TakeWhileSet::new(head.tchar.clone()), |name: &&[u8]| {
+            !name.is_empty()
+        });
+        let fold = Verify::new(
+            And::new(newline(), Verify::new(ows(), |ws: &&[u8]| !ws.is_empty())),
+            move |_: &(&[u8], &[u8])| allow_obs_fold,
+        );
+        let value = And::new(text(), Many0::new(And::new(fold, text())));
+        let value = Map::new(Recognize::new(value), trim_end);
+        // Once a line starts with a field name it has to be a field line.
+        let field = SkipLeft::new(SkipLeft::new(Byte::new(b':'), ows()), value);
+        let field = Skip::new(field, newline());
+        let header = Map::new(And::new(Map::new(name, field_name), Cut::new(field)), header);
+        // A field line left over after the last one is over the limit, see
+        // `ResponseParser::parse_head`.
+        let headers = ManyMN::new(header, 0, head.max_headers);
+
+        Self {
+            block: Arc::new(block),
+            head: Arc::new(And::new(status_line, headers)),
+            newline: Arc::new(newline()),
+        }
+    }
+}
+
+fn status_code(digits: Vec<&u8>) -> u16 {
+    digits
+        .iter()
+        .fold(0, |status, &digit| status * 10 + u16::from(digit - b'0'))
+}
+
+fn or_empty(bytes: Option<&[u8]>) -> &[u8] {
+    bytes.unwrap_or_default()
+}
+
+/// Field names are tokens, which are ASCII only.
+fn field_name(name: &[u8]) -> &str {
+    std::str::from_utf8(name).unwrap()
+}
+
+fn header<'a>((name, value): (&'a str, &'a [u8])) -> Header<'a> {
+    Header { name, value }
+}
+
+/// Removes trailing whitespace from a field value.
+fn trim_end(value: &[u8]) -> &[u8] {
+    let len = value
+        .iter()
+        .rposition(|&b| b != SP && b != HTAB)
+        .map_or(0, |idx| idx + 1);
+
+    &value[..len]
+}
+
+impl Default for ResponseParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Parse<'a, &'a [u8]> for ResponseParser {
+    type Output = Response<'a>;
+
+    fn parse(&self, mut input: &'a [u8]) -> Result<(&'a [u8], Self::Output), ParseError> {
+        loop {
+            let (rest, response) = self.parse_head(input)?;
+
+            if !(self.skip_interim && response.is_interim()) {
+                return Ok((rest, response));
+            }
+
+            input = rest;
+        }
+    }
+}
+
+#[inline]
+pub fn response<'a>() -> Cell<'a, ResponseParser> {
+    Cell::new(ResponseParser::new())
+}
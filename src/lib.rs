@@ -1,24 +1,31 @@
+#![cfg_attr(feature = "portable_simd", feature(portable_simd))]
+
 pub mod cell;
+pub mod error;
+pub mod form;
+pub mod http;
 pub mod parser;
-#[cfg(all(
-    any(target_arch = "x86", target_arch = "x86_64"),
-    target_feature = "sse4.2"
-))]
-pub(crate) mod simd;
+pub mod pratt;
+pub mod report;
+pub mod simd;
+pub mod span;
 pub mod stream;
+pub mod streaming;
+pub mod tuple;
+pub mod unicode;
+pub mod uri;
 
 pub mod prelude {
-    #[cfg(all(
-        any(target_arch = "x86", target_arch = "x86_64"),
-        target_feature = "sse4.2"
-    ))]
-    pub use crate::cell::simd::*;
     pub use crate::cell::*;
-    #[cfg(all(
-        any(target_arch = "x86", target_arch = "x86_64"),
-        target_feature = "sse4.2"
-    ))]
-    pub use crate::parser::simd::*;
+    pub use crate::error::*;
+    pub use crate::parser::simd::{self, TakeTillAny, TakeUntilLiteral, TakeWhileSet};
     pub use crate::parser::*;
+    pub use crate::pratt::*;
+    pub use crate::report::*;
+    pub use crate::simd::ByteSet;
+    pub use crate::span::*;
     pub use crate::stream::*;
+    pub use crate::streaming;
+    pub use crate::tuple::*;
+    pub use crate::unicode::*;
 }
@@ -1,7 +1,15 @@
-pub trait Parse<'a> {
+use crate::error::{Expected, ParseError};
+use crate::span::{Locate, Span};
+use crate::stream::{AsBytes, Input, Text, Token};
+use crate::unicode::next_char;
+use std::borrow::Borrow;
+use std::cell::OnceCell;
+use std::rc::{Rc, Weak};
+
+pub trait Parse<'a, I = &'a [u8]> {
     type Output;
 
-    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]>;
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError>;
 }
 
 pub struct State<F> {
@@ -15,14 +23,14 @@ impl<F> State<F> {
     }
 }
 
-impl<'a, F, T> Parse<'a> for State<F>
+impl<'a, I, F, T> Parse<'a, I> for State<F>
 where
     F: Fn() -> T,
 {
     type Output = T;
 
     #[inline]
-    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
         Ok((input, (self.f)()))
     }
 }
@@ -39,19 +47,15 @@ impl<P, F> Map<P, F> {
     }
 }
 
-impl<'a, P, F, A, B> Parse<'a> for Map<P, F>
+impl<'a, I, P, F, B> Parse<'a, I> for Map<P, F>
 where
-    P: Parse<'a, Output = A>,
-    F: Fn(A) -> B,
+    P: Parse<'a, I>,
+    F: Fn(P::Output) -> B,
 {
     type Output = B;
 
     #[inline]
-    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
-        if input.len() == 0 {
-            return Err(input);
-        }
-
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
         self.parser.parse(input).map(|(next, a)| {
             let b = (self.f)(a);
             (next, b)
@@ -74,15 +78,15 @@ impl<P1, P2> And<P1, P2> {
     }
 }
 
-impl<'a, P1, P2, A, B> Parse<'a> for And<P1, P2>
+impl<'a, I, P1, P2> Parse<'a, I> for And<P1, P2>
 where
-    P1: Parse<'a, Output = A>,
-    P2: Parse<'a, Output = B>,
+    P1: Parse<'a, I>,
+    P2: Parse<'a, I>,
 {
-    type Output = (A, B);
+    type Output = (P1::Output, P2::Output);
 
     #[inline]
-    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
         let (input, a) = self.parser1.parse(input)?;
         let (input, b) = self.parser2.parse(input)?;
 
@@ -105,26 +109,49 @@ impl<P1, P2> Or<P1, P2> {
     }
 }
 
-impl<'a, P1, P2, O> Parse<'a> for Or<P1, P2>
+// Outputs are named through projections rather than extra type parameters,
+// and `or` chains nest on the left so the output is taken from the right.
+// Both keep the trait checks done for deeply nested grammars from growing
+// exponentially with their depth.
+impl<'a, I, P1, P2> Parse<'a, I> for Or<P1, P2>
 where
-    P1: Parse<'a, Output = O>,
-    P2: Parse<'a, Output = O>,
+    I: Copy,
+    P1: Parse<'a, I, Output = P2::Output>,
+    P2: Parse<'a, I>,
 {
-    type Output = O;
+    type Output = P2::Output;
 
     #[inline]
-    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
-        if input.len() == 0 {
-            return Err(input);
-        }
-
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
         match self.parser1.parse(input) {
             res @ Ok(_) => res,
-            Err(_) => self.parser2.parse(input),
+            Err(err1) if !err1.is_recoverable() => Err(err1),
+            Err(err1) => match self.parser2.parse(input) {
+                res @ Ok(_) => res,
+                Err(err2) => Err(err1.merge(err2)),
+            },
         }
     }
 }
 
+/// Runs one step of a repetition.
+///
+/// Recoverable failures and steps that consume no input end the repetition
+/// with `Ok(None)`, incomplete and fatal errors are passed on to the caller.
+#[inline]
+fn step<'a, I, P>(parser: &P, input: I) -> Result<Option<(I, P::Output)>, ParseError>
+where
+    I: Input,
+    P: Parse<'a, I>,
+{
+    match parser.parse(input) {
+        Ok((next, _)) if next.input_len() == input.input_len() => Ok(None),
+        Ok(res) => Ok(Some(res)),
+        Err(err) if !err.is_recoverable() => Err(err),
+        Err(_) => Ok(None),
+    }
+}
+
 pub struct Many0<P> {
     parser: P,
 }
@@ -136,21 +163,18 @@ impl<P> Many0<P> {
     }
 }
 
-impl<'a, P> Parse<'a> for Many0<P>
+impl<'a, I, P> Parse<'a, I> for Many0<P>
 where
-    P: Parse<'a>,
+    I: Input,
+    P: Parse<'a, I>,
 {
     type Output = Vec<P::Output>;
 
     #[inline]
-    fn parse(&self, mut input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
-        if input.len() == 0 {
-            return Err(input);
-        }
-
+    fn parse(&self, mut input: I) -> Result<(I, Self::Output), ParseError> {
         let mut xs = Vec::new();
 
-        while let Ok((next, item)) = self.parser.parse(input) {
+        while let Some((next, item)) = step(&self.parser, input)? {
             xs.push(item);
             input = next;
         }
@@ -171,310 +195,1419 @@ impl<P> Many1<P>
     }
 }
 
-impl<'a, P> Parse<'a> for Many1<P>
+impl<'a, I, P> Parse<'a, I> for Many1<P>
 where
-    P: Parse<'a>,
+    I: Input,
+    P: Parse<'a, I>,
 {
     type Output = Vec<P::Output>;
 
     #[inline]
-    fn parse(&self, mut input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
-        if input.len() == 0 {
-            return Err(input);
-        }
-
+    fn parse(&self, mut input: I) -> Result<(I, Self::Output), ParseError> {
         let mut xs = Vec::new();
 
-        match self.parser.parse(input) {
-            Ok((next, item)) => {
-                xs.push(item);
-                input = next;
-            }
-            Err(_) => {
-                return Err(input);
-            }
-        }
+        let (next, item) = self.parser.parse(input)?;
+        xs.push(item);
+        input = next;
 
-        while let Ok((next, item)) = self.parser.parse(input) {
+        while let Some((next, item)) = step(&self.parser, input)? {
             xs.push(item);
             input = next;
         }
 
-        return Ok((input, xs));
+        Ok((input, xs))
     }
 }
 
-pub struct Skip<P1, P2> {
-    parser1: P1,
-    parser2: P2,
+pub struct ManyMN<P> {
+    parser: P,
+    min: usize,
+    max: usize,
 }
 
-impl<'a, P1, P2> Skip<P1, P2> {
+impl<P> ManyMN<P> {
     #[inline]
-    pub fn new(parser1: P1, parser2: P2) -> Self {
-        Self {
-            parser1,
-            parser2,
-        }
+    pub fn new(parser: P, min: usize, max: usize) -> Self {
+        assert!(min <= max);
+        Self { parser, min, max }
     }
 }
 
-impl<'a, P1, P2> Parse<'a> for Skip<P1, P2>
+impl<'a, I, P> Parse<'a, I> for ManyMN<P>
 where
-    P1: Parse<'a>,
-    P2: Parse<'a>,
+    I: Input,
+    P: Parse<'a, I>,
 {
-    type Output = P1::Output;
+    type Output = Vec<P::Output>;
 
     #[inline]
-    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
-        let (bytes, res) = self.parser1.parse(input)?;
-        let (bytes, _) = self.parser2.parse(bytes)?;
-        Ok((bytes, res))
+    fn parse(&self, mut input: I) -> Result<(I, Self::Output), ParseError> {
+        let mut xs = Vec::new();
+
+        while xs.len() < self.min {
+            let (next, item) = self.parser.parse(input)?;
+            xs.push(item);
+            input = next;
+        }
+
+        while xs.len() < self.max {
+            match step(&self.parser, input)? {
+                Some((next, item)) => {
+                    xs.push(item);
+                    input = next;
+                }
+                None => break,
+            }
+        }
+
+        Ok((input, xs))
     }
 }
 
-pub struct TakeUntil<P> {
+pub struct Count<P> {
     parser: P,
+    count: usize,
 }
 
-impl<P> TakeUntil<P> {
+impl<P> Count<P> {
     #[inline]
-    pub fn new(parser: P) -> Self {
-        Self { parser }
+    pub fn new(parser: P, count: usize) -> Self {
+        Self { parser, count }
     }
 }
 
-impl<'a, P> Parse<'a> for TakeUntil<P>
+impl<'a, I, P> Parse<'a, I> for Count<P>
 where
-    P: Parse<'a>,
+    P: Parse<'a, I>,
 {
-    type Output = &'a [u8];
+    type Output = Vec<P::Output>;
 
     #[inline]
-    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
-        let mut count = 0;
-        let mut temp = input;
-
-        while let Err(xs) = self.parser.parse(temp) {
-            if temp.len() == 0 {
-                return Err(input);
-            }
+    fn parse(&self, mut input: I) -> Result<(I, Self::Output), ParseError> {
+        // The count may come from the input, so only a little is reserved
+        // up front and the rest grows as items are parsed.
+        let mut xs = Vec::with_capacity(self.count.min(64));
 
-            temp = &xs[1..];
-            count += 1;
+        for _ in 0..self.count {
+            let (next, item) = self.parser.parse(input)?;
+            xs.push(item);
+            input = next;
         }
 
-        Ok((&input[count..], &input[0..count]))
+        Ok((input, xs))
     }
 }
 
-pub struct AnyChar;
+pub struct Opt<P> {
+    parser: P,
+}
 
-impl AnyChar {
-    pub fn new() -> Self {
-        Self {}
+impl<P> Opt<P> {
+    #[inline]
+    pub fn new(parser: P) -> Self {
+        Self { parser }
     }
 }
 
-impl<'a> Parse<'a> for AnyChar {
-    type Output = char;
+impl<'a, I, P> Parse<'a, I> for Opt<P>
+where
+    I: Copy,
+    P: Parse<'a, I>,
+{
+    type Output = Option<P::Output>;
 
-    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
-        if input.len() == 0 {
-            return Err(input);
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        match self.parser.parse(input) {
+            Ok((next, item)) => Ok((next, Some(item))),
+            Err(err) if !err.is_recoverable() => Err(err),
+            Err(_) => Ok((input, None)),
         }
+    }
+}
 
-        let ch = input[0] as char;
+/// Zero or more `parser` separated by `sep`, a trailing separator is left
+/// unconsumed.
+pub struct SepBy0<P, S> {
+    parser: P,
+    sep: S,
+}
 
-        match ch.is_ascii_alphabetic() {
-            true => Ok((&input[1..], ch)),
-            false => Err(input),
-        }
+impl<P, S> SepBy0<P, S> {
+    #[inline]
+    pub fn new(parser: P, sep: S) -> Self {
+        Self { parser, sep }
     }
 }
 
-pub struct AnyDigit;
+impl<'a, I, P, S> Parse<'a, I> for SepBy0<P, S>
+where
+    I: Input,
+    P: Parse<'a, I>,
+    S: Parse<'a, I>,
+{
+    type Output = Vec<P::Output>;
 
-impl AnyDigit {
-    pub fn new() -> Self {
-        Self {}
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        match step(&self.parser, input)? {
+            Some((next, item)) => sep_by_rest(&self.parser, &self.sep, next, vec![item]),
+            None => Ok((input, Vec::new())),
+        }
     }
 }
 
-impl<'a> Parse<'a> for AnyDigit {
-    type Output = char;
+/// One or more `parser` separated by `sep`, a trailing separator is left
+/// unconsumed.
+pub struct SepBy1<P, S> {
+    parser: P,
+    sep: S,
+}
 
-    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
-        if input.len() == 0 {
-            return Err(input);
-        }
+impl<P, S> SepBy1<P, S> {
+    #[inline]
+    pub fn new(parser: P, sep: S) -> Self {
+        Self { parser, sep }
+    }
+}
+
+impl<'a, I, P, S> Parse<'a, I> for SepBy1<P, S>
+where
+    I: Input,
+    P: Parse<'a, I>,
+    S: Parse<'a, I>,
+{
+    type Output = Vec<P::Output>;
 
-        let digit = input[0] as char;
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let (next, item) = self.parser.parse(input)?;
+        sep_by_rest(&self.parser, &self.sep, next, vec![item])
+    }
+}
 
-        match digit.is_numeric() {
-            true => Ok((&input[1..], digit)),
-            false => Err(input),
+#[inline]
+fn sep_by_rest<'a, I, P, S>(
+    parser: &P,
+    sep: &S,
+    mut input: I,
+    mut xs: Vec<P::Output>,
+) -> Result<(I, Vec<P::Output>), ParseError>
+where
+    I: Input,
+    P: Parse<'a, I>,
+    S: Parse<'a, I>,
+{
+    loop {
+        let next = match sep.parse(input) {
+            Ok((next, _)) => next,
+            Err(err) if !err.is_recoverable() => return Err(err),
+            Err(_) => break,
+        };
+
+        match step(parser, next)? {
+            Some((next, item)) => {
+                xs.push(item);
+                input = next;
+            }
+            None => break,
         }
     }
+
+    Ok((input, xs))
 }
 
-pub struct Byte {
-    byte: u8,
+/// Zero or more `parser` separated and optionally terminated by `sep`.
+pub struct SepEndBy<P, S> {
+    parser: P,
+    sep: S,
 }
 
-impl Byte {
+impl<P, S> SepEndBy<P, S> {
     #[inline]
-    pub fn new<'a>(byte: u8) -> Self {
-        Self { byte }
+    pub fn new(parser: P, sep: S) -> Self {
+        Self { parser, sep }
     }
 }
 
-impl<'a> Parse<'a> for Byte {
-    type Output = &'a u8;
+impl<'a, I, P, S> Parse<'a, I> for SepEndBy<P, S>
+where
+    I: Input,
+    P: Parse<'a, I>,
+    S: Parse<'a, I>,
+{
+    type Output = Vec<P::Output>;
 
     #[inline]
-    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
-        if input.len() == 0 {
-            return Err(input);
-        }
+    fn parse(&self, mut input: I) -> Result<(I, Self::Output), ParseError> {
+        let mut xs = Vec::new();
+
+        while let Some((next, item)) = step(&self.parser, input)? {
+            xs.push(item);
+            input = next;
 
-        match input[0] == self.byte {
-            true => Ok((&input[1..], &input[0])),
-            false => Err(input),
+            match self.sep.parse(input) {
+                Ok((next, _)) => input = next,
+                Err(err) if !err.is_recoverable() => return Err(err),
+                Err(_) => break,
+            }
         }
+
+        Ok((input, xs))
     }
 }
 
-pub struct Char {
-    ch: char,
+/// Repeats `parser` until `end` succeeds, returning the items and the
+/// output of `end`.
+pub struct ManyTill<P, E> {
+    parser: P,
+    end: E,
 }
 
-impl Char {
+impl<P, E> ManyTill<P, E> {
     #[inline]
-    pub fn new<'a>(ch: char) -> Self {
-        Self { ch }
+    pub fn new(parser: P, end: E) -> Self {
+        Self { parser, end }
     }
 }
 
-impl<'a> Parse<'a> for Char {
-    type Output = char;
+impl<'a, I, P, E> Parse<'a, I> for ManyTill<P, E>
+where
+    I: Input,
+    P: Parse<'a, I>,
+    E: Parse<'a, I>,
+{
+    type Output = (Vec<P::Output>, E::Output);
 
     #[inline]
-    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
-        if input.len() == 0 {
-            return Err(input);
+    fn parse(&self, mut input: I) -> Result<(I, Self::Output), ParseError> {
+        let mut xs = Vec::new();
+
+        loop {
+            let err = match self.end.parse(input) {
+                Ok((next, end)) => return Ok((next, (xs, end))),
+                Err(err) if !err.is_recoverable() => return Err(err),
+                Err(err) => err,
+            };
+
+            match self.parser.parse(input) {
+                Ok((next, _)) if next.input_len() == input.input_len() => return Err(err),
+                Ok((next, item)) => {
+                    xs.push(item);
+                    input = next;
+                }
+                Err(item_err) if !item_err.is_recoverable() => return Err(item_err),
+                Err(item_err) => return Err(err.merge(item_err)),
+            }
         }
+    }
+}
+
+/// Folds zero or more `parser` outputs into an accumulator without
+/// collecting them.
+pub struct FoldMany0<P, Init, F> {
+    parser: P,
+    init: Init,
+    f: F,
+}
+
+impl<P, Init, F> FoldMany0<P, Init, F> {
+    #[inline]
+    pub fn new(parser: P, init: Init, f: F) -> Self {
+        Self { parser, init, f }
+    }
+}
 
-        let ch = input[0] as char;
+impl<'a, I, P, Init, F, B> Parse<'a, I> for FoldMany0<P, Init, F>
+where
+    I: Input,
+    P: Parse<'a, I>,
+    Init: Fn() -> B,
+    F: Fn(B, P::Output) -> B,
+{
+    type Output = B;
+
+    #[inline]
+    fn parse(&self, mut input: I) -> Result<(I, Self::Output), ParseError> {
+        let mut acc = (self.init)();
 
-        match self.ch == ch {
-            true => Ok((&input[1..], ch)),
-            false => Err(input),
+        while let Some((next, item)) = step(&self.parser, input)? {
+            acc = (self.f)(acc, item);
+            input = next;
         }
+
+        Ok((input, acc))
     }
 }
 
-pub struct Slice {
-    bytes: Box<[u8]>,
+pub struct Skip<P1, P2> {
+    parser1: P1,
+    parser2: P2,
 }
 
-impl Slice {
-    pub fn new(slice: &[u8]) -> Self {
+impl<P1, P2> Skip<P1, P2> {
+    #[inline]
+    pub fn new(parser1: P1, parser2: P2) -> Self {
         Self {
-            bytes: slice.into(),
+            parser1,
+            parser2,
         }
     }
+}
 
-    pub fn len(&self) -> usize {
-        self.bytes.len()
+impl<'a, I, P1, P2> Parse<'a, I> for Skip<P1, P2>
+where
+    P1: Parse<'a, I>,
+    P2: Parse<'a, I>,
+{
+    type Output = P1::Output;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let (bytes, res) = self.parser1.parse(input)?;
+        let (bytes, _) = self.parser2.parse(bytes)?;
+        Ok((bytes, res))
     }
 }
 
-impl<'a> Parse<'a> for Slice {
-    type Output = &'a [u8];
+/// Runs both parsers in order and keeps the output of the second.
+pub struct SkipLeft<P1, P2> {
+    parser1: P1,
+    parser2: P2,
+}
 
-    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
-        if input.len() < self.len() {
-            return Err(input);
+impl<P1, P2> SkipLeft<P1, P2> {
+    #[inline]
+    pub fn new(parser1: P1, parser2: P2) -> Self {
+        Self {
+            parser1,
+            parser2,
         }
+    }
+}
 
-        for idx in 0..self.len() {
-            if self.bytes[idx] != input[idx] {
-                return Err(input);
-            }
-        }
-        let output = &input[0..self.len()];
+impl<'a, I, P1, P2> Parse<'a, I> for SkipLeft<P1, P2>
+where
+    P1: Parse<'a, I>,
+    P2: Parse<'a, I>,
+{
+    type Output = P2::Output;
 
-        Ok((&input[self.len()..], output))
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let (bytes, _) = self.parser1.parse(input)?;
+        self.parser2.parse(bytes)
     }
 }
 
-#[cfg(all(
-    any(target_arch = "x86", target_arch = "x86_64"),
-    target_feature = "sse4.2"
-))]
-pub mod simd {
-    use super::*;
-    use crate::simd;
+pub struct TakeUntil<P> {
+    parser: P,
+}
 
-    pub struct Slice {
-        bytes: Box<[u8]>,
+impl<P> TakeUntil<P> {
+    #[inline]
+    pub fn new(parser: P) -> Self {
+        Self { parser }
     }
+}
 
-    impl Slice {
-        pub fn new(bytes: &[u8]) -> Self {
-            assert!(bytes.len() < 16);
-            Self {
-                bytes: bytes.into(),
+impl<'a, I, P> Parse<'a, I> for TakeUntil<P>
+where
+    I: Input,
+    P: Parse<'a, I>,
+{
+    type Output = I;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let mut temp = input;
+
+        while let Err(err) = self.parser.parse(temp) {
+            if !err.is_recoverable() {
+                return Err(err);
             }
-        }
 
-        pub fn len(&self) -> usize {
-            self.bytes.len()
+            match temp.next_item() {
+                Some((_, next)) => temp = next,
+                None => return Err(err),
+            }
         }
-    }
 
-    impl<'a> Parse<'a> for Slice {
-        type Output = &'a [u8];
+        let (output, next) = input.split_at(input.input_len() - temp.input_len());
 
-        fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
-            if input.len() < self.len() {
-                return Err(input);
-            }
+        Ok((next, output))
+    }
+}
 
-            if let None = simd::compare(&input[0..self.len()], &self.bytes) {
-                return Err(input);
-            }
+/// Makes any failure of `parser` fatal, committing to the current branch.
+pub struct Cut<P> {
+    parser: P,
+}
+
+impl<P> Cut<P> {
+    #[inline]
+    pub fn new(parser: P) -> Self {
+        Self { parser }
+    }
+}
 
-            let output = &input[0..self.len()];
+impl<'a, I, P> Parse<'a, I> for Cut<P>
+where
+    P: Parse<'a, I>,
+{
+    type Output = P::Output;
 
-            Ok((&input[self.len()..], output))
-        }
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        self.parser.parse(input).map_err(ParseError::into_fatal)
     }
+}
 
-    pub struct TakeUntilLiteral {
-        bytes: Box<[u8]>,
+/// Type erased parser, keeps the types of large or recursive grammars small.
+pub struct Boxed<'a, O, I = &'a [u8]> {
+    parser: Rc<dyn Parse<'a, I, Output = O> + 'a>,
+}
+
+impl<'a, O, I> Boxed<'a, O, I> {
+    #[inline]
+    pub fn new<P>(parser: P) -> Self
+    where
+        P: Parse<'a, I, Output = O> + 'a,
+    {
+        Self {
+            parser: Rc::new(parser),
+        }
     }
+}
 
-    impl TakeUntilLiteral {
-        pub fn new(bytes: &[u8]) -> Self {
-            assert!(bytes.len() < 16);
-            Self {
-                bytes: bytes.to_vec().into_boxed_slice(),
-            }
+impl<'a, O, I> Clone for Boxed<'a, O, I> {
+    fn clone(&self) -> Self {
+        Self {
+            parser: self.parser.clone(),
         }
     }
+}
 
-    impl<'a> Parse<'a> for TakeUntilLiteral {
-        type Output = &'a [u8];
+impl<'a, O, I> Parse<'a, I> for Boxed<'a, O, I> {
+    type Output = O;
 
-        fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), &'a [u8]> {
-            match simd::compare(input, &self.bytes) {
-                Some(idx) => Ok((&input[idx..], &input[0..idx])),
-                None => Err(input),
-            }
-        }
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        self.parser.parse(input)
     }
 }
 
+/// Builds the parser returned by `f` each time it runs, so a grammar
+/// function can refer to itself.
+pub struct Lazy<F> {
+    f: F,
+}
+
+impl<F> Lazy<F> {
+    #[inline]
+    pub fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+impl<'a, I, F, P> Parse<'a, I> for Lazy<F>
+where
+    F: Fn() -> P,
+    P: Parse<'a, I>,
+{
+    type Output = P::Output;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        (self.f)().parse(input)
+    }
+}
+
+type Slot<'a, O, I> = OnceCell<Boxed<'a, O, I>>;
+
+enum Link<'a, O, I> {
+    Owner(Rc<Slot<'a, O, I>>),
+    Handle(Weak<Slot<'a, O, I>>),
+}
+
+/// Self referential parser built once by [`crate::cell::recursive`].
+///
+/// The definition only holds weak handles to itself, the parser is freed
+/// with the last owning copy.
+pub struct Recursive<'a, O, I = &'a [u8]> {
+    link: Link<'a, O, I>,
+}
+
+impl<'a, O, I> Recursive<'a, O, I> {
+    pub fn new<F, P>(f: F) -> Self
+    where
+        F: FnOnce(Recursive<'a, O, I>) -> P,
+        P: Parse<'a, I, Output = O> + 'a,
+    {
+        let slot = Rc::new(OnceCell::new());
+        let handle = Self {
+            link: Link::Handle(Rc::downgrade(&slot)),
+        };
+
+        let _ = slot.set(Boxed::new(f(handle)));
+
+        Self {
+            link: Link::Owner(slot),
+        }
+    }
+}
+
+impl<'a, O, I> Clone for Recursive<'a, O, I> {
+    fn clone(&self) -> Self {
+        let link = match &self.link {
+            Link::Owner(slot) => Link::Owner(slot.clone()),
+            Link::Handle(slot) => Link::Handle(slot.clone()),
+        };
+
+        Self { link }
+    }
+}
+
+impl<'a, O, I> Parse<'a, I> for Recursive<'a, O, I> {
+    type Output = O;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let parser = match &self.link {
+            Link::Owner(slot) => slot.get().cloned(),
+            Link::Handle(slot) => slot
+                .upgrade()
+                .expect("recursive parser used after it was dropped")
+                .get()
+                .cloned(),
+        };
+
+        parser
+            .expect("recursive parser used before it was defined")
+            .parse(input)
+    }
+}
+
+/// Pairs the output of `parser` with the span of input it consumed.
+pub struct Spanned<P> {
+    parser: P,
+}
+
+impl<P> Spanned<P> {
+    #[inline]
+    pub fn new(parser: P) -> Self {
+        Self { parser }
+    }
+}
+
+impl<'a, I, P> Parse<'a, I> for Spanned<P>
+where
+    I: Locate,
+    P: Parse<'a, I>,
+{
+    type Output = (P::Output, Span);
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let start = input.location_offset();
+        let (next, output) = self.parser.parse(input)?;
+        let span = Span::new(start, next.location_offset());
+
+        Ok((next, (output, span)))
+    }
+}
+
+/// Returns the input consumed by `parser` instead of its output.
+pub struct Recognize<P> {
+    parser: P,
+}
+
+impl<P> Recognize<P> {
+    #[inline]
+    pub fn new(parser: P) -> Self {
+        Self { parser }
+    }
+}
+
+impl<'a, I, P> Parse<'a, I> for Recognize<P>
+where
+    I: Input,
+    P: Parse<'a, I>,
+{
+    type Output = I;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let (next, _) = self.parser.parse(input)?;
+        let (output, _) = input.split_at(input.input_len() - next.input_len());
+
+        Ok((next, output))
+    }
+}
+
+/// Maps the output through a fallible function, failing at the start of the
+/// input when it returns an error.
+pub struct MapRes<P, F> {
+    parser: P,
+    f: F,
+}
+
+impl<P, F> MapRes<P, F> {
+    #[inline]
+    pub fn new(parser: P, f: F) -> Self {
+        Self { parser, f }
+    }
+}
+
+impl<'a, I, P, F, B, E> Parse<'a, I> for MapRes<P, F>
+where
+    I: Input,
+    P: Parse<'a, I>,
+    F: Fn(P::Output) -> Result<B, E>,
+{
+    type Output = B;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let (next, a) = self.parser.parse(input)?;
+
+        match (self.f)(a) {
+            Ok(b) => Ok((next, b)),
+            Err(_) => Err(ParseError::unexpected(input)),
+        }
+    }
+}
+
+/// Runs `parser2` over the output of `parser1`, which must be a slice of
+/// the input.
+///
+/// Errors of `parser2` are moved to the matching position of the outer
+/// input, and since the output is complete they are never incomplete.
+pub struct AndThen<P1, P2> {
+    parser1: P1,
+    parser2: P2,
+}
+
+impl<P1, P2> AndThen<P1, P2> {
+    #[inline]
+    pub fn new(parser1: P1, parser2: P2) -> Self {
+        Self {
+            parser1,
+            parser2,
+        }
+    }
+}
+
+impl<'a, I, P1, P2> Parse<'a, I> for AndThen<P1, P2>
+where
+    I: Input + AsBytes,
+    P1: Parse<'a, I>,
+    P1::Output: Input + AsBytes,
+    P2: Parse<'a, P1::Output>,
+{
+    type Output = P2::Output;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let (next, inner) = self.parser1.parse(input)?;
+
+        match self.parser2.parse(inner) {
+            Ok((_, output)) => Ok((next, output)),
+            Err(err) => {
+                // The output need not start at `input`, as with `>>`,
+                // and an output that is not part of `input` at all reports
+                // at its start.
+                let at = (inner.as_bytes().as_ptr() as usize)
+                    .checked_sub(input.as_bytes().as_ptr() as usize)
+                    .and_then(|start| input.split_at_checked(start + err.offset(inner)))
+                    .map_or(input, |(_, at)| at);
+                Err(err.into_complete().at(at))
+            }
+        }
+    }
+}
+
+/// Builds the next parser from the output of `parser`, as needed for
+/// length prefixed data.
+pub struct FlatMap<P, F> {
+    parser: P,
+    f: F,
+}
+
+impl<P, F> FlatMap<P, F> {
+    #[inline]
+    pub fn new(parser: P, f: F) -> Self {
+        Self { parser, f }
+    }
+}
+
+impl<'a, I, P, F, P2> Parse<'a, I> for FlatMap<P, F>
+where
+    P: Parse<'a, I>,
+    F: Fn(P::Output) -> P2,
+    P2: Parse<'a, I>,
+{
+    type Output = P2::Output;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let (next, output) = self.parser.parse(input)?;
+        (self.f)(output).parse(next)
+    }
+}
+
+/// Replaces the output of `parser` with a clone of `value`.
+pub struct Value<P, T> {
+    parser: P,
+    value: T,
+}
+
+impl<P, T> Value<P, T> {
+    #[inline]
+    pub fn new(parser: P, value: T) -> Self {
+        Self { parser, value }
+    }
+}
+
+impl<'a, I, P, T> Parse<'a, I> for Value<P, T>
+where
+    P: Parse<'a, I>,
+    T: Clone,
+{
+    type Output = T;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let (next, _) = self.parser.parse(input)?;
+        Ok((next, self.value.clone()))
+    }
+}
+
+/// Fails at the start of the input when `f` rejects the output.
+pub struct Verify<P, F> {
+    parser: P,
+    f: F,
+}
+
+impl<P, F> Verify<P, F> {
+    #[inline]
+    pub fn new(parser: P, f: F) -> Self {
+        Self { parser, f }
+    }
+}
+
+impl<'a, I, P, F> Parse<'a, I> for Verify<P, F>
+where
+    I: Input,
+    P: Parse<'a, I>,
+    F: Fn(&P::Output) -> bool,
+{
+    type Output = P::Output;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let (next, output) = self.parser.parse(input)?;
+
+        match (self.f)(&output) {
+            true => Ok((next, output)),
+            false => Err(ParseError::unexpected(input)),
+        }
+    }
+}
+
+/// Adds a description of what was being parsed to errors of `parser`.
+pub struct Context<P> {
+    parser: P,
+    context: &'static str,
+}
+
+impl<P> Context<P> {
+    #[inline]
+    pub fn new(parser: P, context: &'static str) -> Self {
+        Self { parser, context }
+    }
+}
+
+impl<'a, I, P> Parse<'a, I> for Context<P>
+where
+    P: Parse<'a, I>,
+{
+    type Output = P::Output;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        self.parser
+            .parse(input)
+            .map_err(|err| err.add_context(self.context))
+    }
+}
+
+pub struct AnyChar;
+
+impl AnyChar {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for AnyChar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, I> Parse<'a, I> for AnyChar
+where
+    I: Text,
+{
+    type Output = char;
+
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let (ch, next) = next_char(input, Expected::Alphabetic)?;
+
+        match ch.is_ascii_alphabetic() {
+            true => Ok((next, ch)),
+            false => Err(ParseError::new(input, Expected::Alphabetic)),
+        }
+    }
+}
+
+pub struct AnyDigit;
+
+impl AnyDigit {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for AnyDigit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, I> Parse<'a, I> for AnyDigit
+where
+    I: Text,
+{
+    type Output = char;
+
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let (digit, next) = next_char(input, Expected::Digit)?;
+
+        match digit.is_ascii_digit() {
+            true => Ok((next, digit)),
+            false => Err(ParseError::new(input, Expected::Digit)),
+        }
+    }
+}
+
+pub struct Byte {
+    byte: u8,
+}
+
+impl Byte {
+    #[inline]
+    pub fn new(byte: u8) -> Self {
+        Self { byte }
+    }
+}
+
+impl<'a, I> Parse<'a, I> for Byte
+where
+    I: Input<Item = &'a u8>,
+{
+    type Output = &'a u8;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        match input.next_item() {
+            Some((byte, next)) if *byte == self.byte => Ok((next, byte)),
+            _ => Err(ParseError::new(input, Expected::Byte(self.byte))),
+        }
+    }
+}
+
+pub struct Char {
+    ch: char,
+}
+
+impl Char {
+    #[inline]
+    pub fn new(ch: char) -> Self {
+        Self { ch }
+    }
+}
+
+impl<'a, I> Parse<'a, I> for Char
+where
+    I: Text,
+{
+    type Output = char;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        match next_char(input, Expected::Char(self.ch))? {
+            (ch, next) if ch == self.ch => Ok((next, ch)),
+            _ => Err(ParseError::new(input, Expected::Char(self.ch))),
+        }
+    }
+}
+
+pub struct Slice {
+    bytes: Box<[u8]>,
+}
+
+impl Slice {
+    pub fn new(slice: &[u8]) -> Self {
+        Self {
+            bytes: slice.into(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+impl<'a, I> Parse<'a, I> for Slice
+where
+    I: Input + AsBytes,
+{
+    type Output = I;
+
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        // A literal ending inside a multi-byte `char` does not match `&str`
+        // input, even though the bytes do.
+        match input.split_at_checked(self.len()) {
+            Some((output, next)) if output.as_bytes() == &self.bytes[..] => Ok((next, output)),
+            _ => Err(ParseError::new(input, Expected::Slice(self.bytes.clone()))),
+        }
+    }
+}
+
+/// Byte trie over a set of literals. The root dispatches on the first byte
+/// through a table, deeper nodes keep their edges sorted.
+struct Trie {
+    root: Box<[u32; 256]>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Default)]
+struct Node {
+    edges: Vec<(u8, u32)>,
+    literal: Option<usize>,
+}
+
+impl Trie {
+    fn new<'l>(literals: impl Iterator<Item = &'l [u8]>) -> Self {
+        let mut trie = Self {
+            root: Box::new([0; 256]),
+            nodes: vec![Node::default()],
+        };
+
+        for (idx, literal) in literals.enumerate() {
+            let mut node = 0;
+
+            for &byte in literal {
+                node = match trie.child(node, byte) {
+                    Some(child) => child,
+                    None => trie.insert(node, byte),
+                };
+            }
+
+            // The first of duplicate literals wins.
+            trie.nodes[node].literal.get_or_insert(idx);
+        }
+
+        trie
+    }
+
+    fn insert(&mut self, node: usize, byte: u8) -> usize {
+        let child = self.nodes.len();
+        self.nodes.push(Node::default());
+
+        if node == 0 {
+            self.root[byte as usize] = child as u32;
+        } else {
+            let edges = &mut self.nodes[node].edges;
+            let pos = edges.binary_search_by_key(&byte, |&(b, _)| b).unwrap_err();
+            edges.insert(pos, (byte, child as u32));
+        }
+
+        child
+    }
+
+    #[inline]
+    fn child(&self, node: usize, byte: u8) -> Option<usize> {
+        if node == 0 {
+            return match self.root[byte as usize] {
+                0 => None,
+                child => Some(child as usize),
+            };
+        }
+
+        let edges = &self.nodes[node].edges;
+        edges
+            .binary_search_by_key(&byte, |&(b, _)| b)
+            .ok()
+            .map(|pos| edges[pos].1 as usize)
+    }
+
+    /// Length and index of the longest literal that `bytes` starts with and
+    /// whose length `fits`.
+    #[inline]
+    fn longest(&self, bytes: &[u8], fits: impl Fn(usize) -> bool) -> Option<(usize, usize)> {
+        let mut node = 0;
+        let mut longest = self.nodes[0].literal.map(|idx| (0, idx));
+
+        for (len, &byte) in bytes.iter().enumerate() {
+            node = match self.child(node, byte) {
+                Some(child) => child,
+                None => break,
+            };
+
+            match self.nodes[node].literal {
+                Some(idx) if fits(len + 1) => longest = Some((len + 1, idx)),
+                _ => {}
+            }
+        }
+
+        longest
+    }
+}
+
+/// Error listing every literal, as a chain of `or` would report it.
+fn expected_literals<I: Input>(input: I, literals: &[Box<[u8]>]) -> ParseError {
+    let mut literals = literals.iter();
+    let first = literals.next().cloned().unwrap_or_default();
+    let err = ParseError::new(input, Expected::Slice(first));
+
+    literals.fold(err, |err, literal| {
+        err.merge(ParseError::new(input, Expected::Slice(literal.clone())))
+    })
+}
+
+/// Longest of a set of literals, found in a single pass over the input
+/// instead of trying each literal in turn. The set must not be empty.
+pub struct OneOfLiterals {
+    trie: Trie,
+    literals: Vec<Box<[u8]>>,
+}
+
+impl OneOfLiterals {
+    pub fn new(literals: &[&[u8]]) -> Self {
+        assert!(!literals.is_empty(), "no literals to match");
+        Self {
+            trie: Trie::new(literals.iter().copied()),
+            literals: literals.iter().map(|&literal| literal.into()).collect(),
+        }
+    }
+}
+
+impl<'a, I> Parse<'a, I> for OneOfLiterals
+where
+    I: Input + AsBytes,
+{
+    type Output = I;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        // A literal that ends inside a char of `&str` input does not match.
+        let fits = |len| input.split_at_checked(len).is_some();
+
+        match self.trie.longest(input.as_bytes(), fits) {
+            Some((len, _)) => {
+                let (output, next) = input.split_at(len);
+                Ok((next, output))
+            }
+            None => Err(expected_literals(input, &self.literals)),
+        }
+    }
+}
+
+/// Like [`OneOfLiterals`], but outputs the value paired with the literal
+/// that matched.
+pub struct Keywords<T> {
+    trie: Trie,
+    literals: Vec<Box<[u8]>>,
+    values: Vec<T>,
+}
+
+impl<T: Clone> Keywords<T> {
+    pub fn new(keywords: &[(&[u8], T)]) -> Self {
+        assert!(!keywords.is_empty(), "no keywords to match");
+        Self {
+            trie: Trie::new(keywords.iter().map(|&(literal, _)| literal)),
+            literals: keywords.iter().map(|&(literal, _)| literal.into()).collect(),
+            values: keywords.iter().map(|(_, value)| value.clone()).collect(),
+        }
+    }
+}
+
+impl<'a, I, T> Parse<'a, I> for Keywords<T>
+where
+    I: Input + AsBytes,
+    T: Clone,
+{
+    type Output = T;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let fits = |len| input.split_at_checked(len).is_some();
+
+        match self.trie.longest(input.as_bytes(), fits) {
+            Some((len, idx)) => {
+                let (_, next) = input.split_at(len);
+                Ok((next, self.values[idx].clone()))
+            }
+            None => Err(expected_literals(input, &self.literals)),
+        }
+    }
+}
+
+/// Matches a single item equal to `token`, for token inputs such as the
+/// output of a lexer.
+pub struct Just<T> {
+    token: T,
+}
+
+impl<T> Just<T> {
+    #[inline]
+    pub fn new(token: T) -> Self {
+        Self { token }
+    }
+}
+
+impl<'a, I, T> Parse<'a, I> for Just<T>
+where
+    I: Input,
+    I::Item: Borrow<T>,
+    T: Token + PartialEq,
+{
+    type Output = I::Item;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        match input.next_item() {
+            Some((item, next)) if *item.borrow() == self.token => Ok((next, item)),
+            _ => Err(ParseError::new(input, self.token.found().into())),
+        }
+    }
+}
+
+/// Matches a single item accepted by `f`.
+pub struct Satisfy<F> {
+    f: F,
+}
+
+impl<F> Satisfy<F> {
+    #[inline]
+    pub fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+impl<'a, I, F> Parse<'a, I> for Satisfy<F>
+where
+    I: Input,
+    F: Fn(I::Item) -> bool,
+{
+    type Output = I::Item;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        match input.next_item() {
+            Some((item, next)) if (self.f)(item) => Ok((next, item)),
+            _ => Err(ParseError::unexpected(input)),
+        }
+    }
+}
+
+/// Literal parsers backed by [`crate::simd`], which picks the search
+/// implementation at runtime.
+pub mod simd {
+    use super::*;
+    use crate::simd::{self, Backend, ByteSet};
+
+    pub struct Slice {
+        bytes: Box<[u8]>,
+        backend: Backend,
+    }
+
+    impl Slice {
+        pub fn new(bytes: &[u8]) -> Self {
+            Self::with_backend(bytes, simd::backend())
+        }
+
+        /// Uses `backend` instead of the detected one, panics if the CPU
+        /// does not support it.
+        pub fn with_backend(bytes: &[u8], backend: Backend) -> Self {
+            assert!(backend.is_available());
+            Self {
+                bytes: bytes.into(),
+                backend,
+            }
+        }
+
+        pub fn len(&self) -> usize {
+            self.bytes.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.bytes.is_empty()
+        }
+    }
+
+    impl<'a, I> Parse<'a, I> for Slice
+    where
+        I: Input + AsBytes,
+    {
+        type Output = I;
+
+        fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+            let bytes = input.as_bytes();
+
+            if bytes.len() < self.len()
+                || self.backend.find(&bytes[0..self.len()], &self.bytes) != Some(0)
+            {
+                return Err(ParseError::new(input, Expected::Slice(self.bytes.clone())));
+            }
+
+            match input.split_at_checked(self.len()) {
+                Some((output, next)) => Ok((next, output)),
+                None => Err(ParseError::new(input, Expected::Slice(self.bytes.clone()))),
+            }
+        }
+    }
+
+    pub struct TakeUntilLiteral {
+        bytes: Box<[u8]>,
+        backend: Backend,
+    }
+
+    impl TakeUntilLiteral {
+        pub fn new(bytes: &[u8]) -> Self {
+            Self::with_backend(bytes, simd::backend())
+        }
+
+        /// Uses `backend` instead of the detected one, panics if the CPU
+        /// does not support it.
+        pub fn with_backend(bytes: &[u8], backend: Backend) -> Self {
+            assert!(backend.is_available());
+            Self {
+                bytes: bytes.to_vec().into_boxed_slice(),
+                backend,
+            }
+        }
+    }
+
+    impl<'a, I> Parse<'a, I> for TakeUntilLiteral
+    where
+        I: Input + AsBytes,
+    {
+        type Output = I;
+
+        fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+            // A literal found inside a multi-byte `char` of a `&str` starts
+            // with a continuation byte, so it cannot occur on a boundary.
+            let found = self.backend.find(input.as_bytes(), &self.bytes);
+
+            match found.and_then(|idx| input.split_at_checked(idx)) {
+                Some((output, next)) => Ok((next, output)),
+                None => Err(ParseError::new(
+                    input.split_at(input.input_len()).1,
+                    Expected::Slice(self.bytes.clone()),
+                )),
+            }
+        }
+    }
+
+    /// Splits at an offset found by a byte class search, failing at the
+    /// start of the `char` when the offset is inside one.
+    fn split_class<I: Input>(input: I, idx: usize) -> Result<(I, I), ParseError> {
+        input.split_at_checked(idx).ok_or_else(|| {
+            let at = (0..idx).rev().find_map(|at| input.split_at_checked(at));
+            ParseError::unexpected(at.map_or(input, |(_, at)| at))
+        })
+    }
+
+    /// Longest prefix made of bytes in `set`, possibly empty.
+    ///
+    /// On `&str` input a set with non-ASCII bytes can stop inside a
+    /// multi-byte `char`, which fails at the start of that `char`.
+    pub struct TakeWhileSet {
+        set: ByteSet,
+        backend: Backend,
+    }
+
+    impl TakeWhileSet {
+        pub fn new(set: ByteSet) -> Self {
+            Self::with_backend(set, simd::backend())
+        }
+
+        pub fn with_backend(set: ByteSet, backend: Backend) -> Self {
+            assert!(backend.is_available());
+            Self { set, backend }
+        }
+    }
+
+    impl<'a, I> Parse<'a, I> for TakeWhileSet
+    where
+        I: Input + AsBytes,
+    {
+        type Output = I;
+
+        fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+            let idx = self
+                .backend
+                .find_not(input.as_bytes(), &self.set)
+                .unwrap_or_else(|| input.input_len());
+            let (output, next) = split_class(input, idx)?;
+
+            Ok((next, output))
+        }
+    }
+
+    /// Longest prefix without any byte of `set`, which is the whole input
+    /// when none of them occur.
+    ///
+    /// On `&str` input a set with non-ASCII bytes can stop inside a
+    /// multi-byte `char`, which fails at the start of that `char`.
+    pub struct TakeTillAny {
+        set: ByteSet,
+        backend: Backend,
+    }
+
+    impl TakeTillAny {
+        pub fn new(set: ByteSet) -> Self {
+            Self::with_backend(set, simd::backend())
+        }
+
+        pub fn with_backend(set: ByteSet, backend: Backend) -> Self {
+            assert!(backend.is_available());
+            Self { set, backend }
+        }
+    }
+
+    impl<'a, I> Parse<'a, I> for TakeTillAny
+    where
+        I: Input + AsBytes,
+    {
+        type Output = I;
+
+        fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+            let idx = self
+                .backend
+                .find_any(input.as_bytes(), &self.set)
+                .unwrap_or_else(|| input.input_len());
+            let (output, next) = split_class(input, idx)?;
+
+            Ok((next, output))
+        }
+    }
+}
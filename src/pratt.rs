@@ -0,0 +1,336 @@
+//! Operator precedence expressions.
+//!
+//! [`Pratt`] parses an atom followed by any number of operators from its
+//! table, folding each operator into the result with its callback. Powers
+//! are precedences, higher powers bind tighter.
+//!
+//! ```
+//! use parsing::prelude::*;
+//!
+//! let number = many1(any_digit())
+//!     .map(|digits| digits.iter().collect::<String>().parse::<i64>().unwrap());
+//! let expr = pratt(number)
+//!     .prefix(chr('-'), 3, |_, x| -x)
+//!     .infix(chr('+'), Assoc::Left, 1, |_, l, r| l + r)
+//!     .infix(chr('*'), Assoc::Left, 2, |_, l, r| l * r)
+//!     .infix(chr('^'), Assoc::Right, 4, |_, l, r| l.pow(r as u32));
+//!
+//! let (_, value) = expr.parse("-2+3*2^3^0".to_stream()).unwrap();
+//! assert_eq!(value, 4);
+//! ```
+
+use crate::cell::Cell;
+use crate::error::ParseError;
+use crate::parser::Parse;
+use crate::stream::Input;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Assoc {
+    Left,
+    Right,
+}
+
+enum Failed<O> {
+    /// The operator did not match, hands back the left operand.
+    NoMatch(O, ParseError),
+    /// The operator matched but the rest of the expression did not.
+    Error(ParseError),
+}
+
+type Operand<'r, I, O> = &'r dyn Fn(I, u32) -> Result<(I, O), ParseError>;
+
+trait PrefixRule<'a, I, O> {
+    fn apply(&self, input: I, operand: Operand<I, O>) -> Result<(I, O), Failed<()>>;
+}
+
+trait InfixRule<'a, I, O> {
+    fn left_power(&self) -> u32;
+    fn apply(&self, input: I, lhs: O, operand: Operand<I, O>) -> Result<(I, O), Failed<O>>;
+}
+
+trait PostfixRule<'a, I, O> {
+    fn left_power(&self) -> u32;
+    fn apply(&self, input: I, lhs: O) -> Result<(I, O), Failed<O>>;
+}
+
+struct Prefix<P, F> {
+    parser: P,
+    power: u32,
+    fold: F,
+}
+
+impl<'a, I, O, P, F> PrefixRule<'a, I, O> for Prefix<P, F>
+where
+    I: Input,
+    P: Parse<'a, I>,
+    F: Fn(P::Output, O) -> O,
+{
+    fn apply(&self, input: I, operand: Operand<I, O>) -> Result<(I, O), Failed<()>> {
+        let (input, op) = match self.parser.parse(input) {
+            // An operator that consumes nothing would be followed by the
+            // same operand forever.
+            Ok((next, _)) if next.input_len() == input.input_len() => {
+                return Err(Failed::NoMatch((), ParseError::unexpected(input)));
+            }
+            Ok(res) => res,
+            Err(err) if !err.is_recoverable() => return Err(Failed::Error(err)),
+            Err(err) => return Err(Failed::NoMatch((), err)),
+        };
+        let (input, rhs) = operand(input, self.power * 2).map_err(Failed::Error)?;
+
+        Ok((input, (self.fold)(op, rhs)))
+    }
+}
+
+struct Infix<P, F> {
+    parser: P,
+    assoc: Assoc,
+    power: u32,
+    fold: F,
+}
+
+impl<'a, I, O, P, F> InfixRule<'a, I, O> for Infix<P, F>
+where
+    I: Input,
+    P: Parse<'a, I>,
+    F: Fn(P::Output, O, O) -> O,
+{
+    fn left_power(&self) -> u32 {
+        match self.assoc {
+            Assoc::Left => self.power * 2,
+            Assoc::Right => self.power * 2 + 1,
+        }
+    }
+
+    fn apply(&self, input: I, lhs: O, operand: Operand<I, O>) -> Result<(I, O), Failed<O>> {
+        let (input, op) = match self.parser.parse(input) {
+            // An operator that consumes nothing would match again forever.
+            Ok((next, _)) if next.input_len() == input.input_len() => {
+                return Err(Failed::NoMatch(lhs, ParseError::unexpected(input)));
+            }
+            Ok(res) => res,
+            Err(err) if !err.is_recoverable() => return Err(Failed::Error(err)),
+            Err(err) => return Err(Failed::NoMatch(lhs, err)),
+        };
+        let right_power = match self.assoc {
+            Assoc::Left => self.power * 2 + 1,
+            Assoc::Right => self.power * 2,
+        };
+        let (input, rhs) = operand(input, right_power).map_err(Failed::Error)?;
+
+        Ok((input, (self.fold)(op, lhs, rhs)))
+    }
+}
+
+struct Postfix<P, F> {
+    parser: P,
+    power: u32,
+    fold: F,
+}
+
+impl<'a, I, O, P, F> PostfixRule<'a, I, O> for Postfix<P, F>
+where
+    I: Input,
+    P: Parse<'a, I>,
+    F: Fn(P::Output, O) -> O,
+{
+    fn left_power(&self) -> u32 {
+        self.power * 2
+    }
+
+    fn apply(&self, input: I, lhs: O) -> Result<(I, O), Failed<O>> {
+        match self.parser.parse(input) {
+            // An operator that consumes nothing would match again forever.
+            Ok((next, _)) if next.input_len() == input.input_len() => {
+                Err(Failed::NoMatch(lhs, ParseError::unexpected(input)))
+            }
+            Ok((input, op)) => Ok((input, (self.fold)(op, lhs))),
+            Err(err) if !err.is_recoverable() => Err(Failed::Error(err)),
+            Err(err) => Err(Failed::NoMatch(lhs, err)),
+        }
+    }
+}
+
+/// Expression parser built from an atom and a table of operators.
+pub struct Pratt<'a, A, O, I = &'a [u8]> {
+    atom: A,
+    prefix: Vec<Box<dyn PrefixRule<'a, I, O> + 'a>>,
+    infix: Vec<Box<dyn InfixRule<'a, I, O> + 'a>>,
+    postfix: Vec<Box<dyn PostfixRule<'a, I, O> + 'a>>,
+}
+
+impl<'a, A, O, I> Pratt<'a, A, O, I>
+where
+    A: Parse<'a, I, Output = O>,
+    I: Input,
+{
+    pub fn new(atom: A) -> Self {
+        Self {
+            atom,
+            prefix: Vec::new(),
+            infix: Vec::new(),
+            postfix: Vec::new(),
+        }
+    }
+
+    pub fn prefix<P, F>(mut self, parser: P, power: u32, fold: F) -> Self
+    where
+        P: Parse<'a, I> + 'a,
+        F: Fn(P::Output, O) -> O + 'a,
+    {
+        self.prefix.push(Box::new(Prefix {
+            parser,
+            power,
+            fold,
+        }));
+        self
+    }
+
+    pub fn infix<P, F>(mut self, parser: P, assoc: Assoc, power: u32, fold: F) -> Self
+    where
+        P: Parse<'a, I> + 'a,
+        F: Fn(P::Output, O, O) -> O + 'a,
+    {
+        self.infix.push(Box::new(Infix {
+            parser,
+            assoc,
+            power,
+            fold,
+        }));
+        self
+    }
+
+    pub fn postfix<P, F>(mut self, parser: P, power: u32, fold: F) -> Self
+    where
+        P: Parse<'a, I> + 'a,
+        F: Fn(P::Output, O) -> O + 'a,
+    {
+        self.postfix.push(Box::new(Postfix {
+            parser,
+            power,
+            fold,
+        }));
+        self
+    }
+
+    fn operand(&self, input: I) -> Result<(I, O), ParseError> {
+        let operand = |input, power| self.expression(input, power);
+        let mut errors: Option<ParseError> = None;
+
+        for rule in &self.prefix {
+            match rule.apply(input, &operand) {
+                Ok(res) => return Ok(res),
+                Err(Failed::NoMatch(_, err)) => {
+                    errors = Some(match errors {
+                        Some(errors) => errors.merge(err),
+                        None => err,
+                    })
+                }
+                Err(Failed::Error(err)) => return Err(err),
+            }
+        }
+
+        self.atom.parse(input).map_err(|err| match errors {
+            Some(errors) => errors.merge(err),
+            None => err,
+        })
+    }
+
+    fn expression(&self, input: I, min_power: u32) -> Result<(I, O), ParseError> {
+        let operand = |input, power| self.expression(input, power);
+        let (mut input, mut lhs) = self.operand(input)?;
+
+        'outer: loop {
+            for rule in &self.postfix {
+                if rule.left_power() < min_power {
+                    continue;
+                }
+
+                match rule.apply(input, lhs) {
+                    Ok((next, value)) => {
+                        input = next;
+                        lhs = value;
+                        continue 'outer;
+                    }
+                    Err(Failed::NoMatch(value, _)) => lhs = value,
+                    Err(Failed::Error(err)) => return Err(err),
+                }
+            }
+
+            for rule in &self.infix {
+                if rule.left_power() < min_power {
+                    continue;
+                }
+
+                match rule.apply(input, lhs, &operand) {
+                    Ok((next, value)) => {
+                        input = next;
+                        lhs = value;
+                        continue 'outer;
+                    }
+                    Err(Failed::NoMatch(value, _)) => lhs = value,
+                    Err(Failed::Error(err)) => return Err(err),
+                }
+            }
+
+            return Ok((input, lhs));
+        }
+    }
+}
+
+impl<'a, A, O, I> Parse<'a, I> for Pratt<'a, A, O, I>
+where
+    A: Parse<'a, I, Output = O>,
+    I: Input,
+{
+    type Output = O;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        self.expression(input, 0)
+    }
+}
+
+/// Operator table methods for the cell returned by [`pratt`], so the table
+/// can be built without unwrapping it.
+impl<'a, A, O, I> Cell<'a, Pratt<'a, A, O, I>, I>
+where
+    A: Parse<'a, I, Output = O>,
+    I: Input,
+{
+    #[inline]
+    pub fn prefix<P, F>(self, parser: P, power: u32, fold: F) -> Self
+    where
+        P: Parse<'a, I> + 'a,
+        F: Fn(P::Output, O) -> O + 'a,
+    {
+        Cell::new(self.take().prefix(parser, power, fold))
+    }
+
+    #[inline]
+    pub fn infix<P, F>(self, parser: P, assoc: Assoc, power: u32, fold: F) -> Self
+    where
+        P: Parse<'a, I> + 'a,
+        F: Fn(P::Output, O, O) -> O + 'a,
+    {
+        Cell::new(self.take().infix(parser, assoc, power, fold))
+    }
+
+    #[inline]
+    pub fn postfix<P, F>(self, parser: P, power: u32, fold: F) -> Self
+    where
+        P: Parse<'a, I> + 'a,
+        F: Fn(P::Output, O) -> O + 'a,
+    {
+        Cell::new(self.take().postfix(parser, power, fold))
+    }
+}
+
+#[inline]
+pub fn pratt<'a, A, O, I>(atom: A) -> Cell<'a, Pratt<'a, A, O, I>, I>
+where
+    A: Parse<'a, I, Output = O>,
+    I: Input,
+{
+    Cell::new(Pratt::new(atom))
+}
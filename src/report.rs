@@ -0,0 +1,111 @@
+use crate::error::ParseError;
+use std::fmt;
+
+/// One-based line and column of a byte offset, columns count UTF-8 characters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    pub fn new(input: &[u8], offset: usize) -> Self {
+        let offset = offset.min(input.len());
+        let start = line_start(input, offset);
+
+        Self {
+            line: input[..start].iter().filter(|&&b| b == b'\n').count() + 1,
+            column: char_count(&input[start..offset]) + 1,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Compiler style rendering of an error against the input it came from.
+///
+/// ```text
+/// error: expected ':', found ' '
+///  --> 2:5
+///   |
+/// 2 | Host example.com
+///   |     ^
+/// ```
+pub struct Report<'a> {
+    input: &'a [u8],
+    offset: usize,
+    message: String,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(input: &'a [u8], err: &ParseError) -> Self {
+        Self::with_message(input, err.offset(input), err.to_string())
+    }
+
+    pub fn with_message<M: Into<String>>(input: &'a [u8], offset: usize, message: M) -> Self {
+        Self {
+            input,
+            offset: offset.min(input.len()),
+            message: message.into(),
+        }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn location(&self) -> Location {
+        Location::new(self.input, self.offset)
+    }
+
+    pub fn line(&self) -> &'a [u8] {
+        let start = line_start(self.input, self.offset);
+        let end = match self.input[self.offset..].iter().position(|&b| b == b'\n') {
+            Some(idx) => self.offset + idx,
+            None => self.input.len(),
+        };
+        let line = &self.input[start..end];
+
+        line.strip_suffix(b"\r").unwrap_or(line)
+    }
+}
+
+impl<'a> fmt::Display for Report<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let location = self.location();
+        let gutter = location.line.to_string().len();
+
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{:w$}--> {}", "", location, w = gutter)?;
+        writeln!(f, "{:w$} |", "", w = gutter)?;
+        writeln!(
+            f,
+            "{} | {}",
+            location.line,
+            String::from_utf8_lossy(self.line())
+        )?;
+        write!(
+            f,
+            "{:w$} | {:c$}^",
+            "",
+            "",
+            w = gutter,
+            c = location.column - 1
+        )
+    }
+}
+
+fn line_start(input: &[u8], offset: usize) -> usize {
+    match input[..offset].iter().rposition(|&b| b == b'\n') {
+        Some(idx) => idx + 1,
+        None => 0,
+    }
+}
+
+fn char_count(bytes: &[u8]) -> usize {
+    bytes.iter().filter(|&&b| b & 0xc0 != 0x80).count()
+}
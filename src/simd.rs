@@ -1,24 +1,784 @@
-pub fn compare(haystack: &[u8], needle: &[u8]) -> Option<usize> {
-    assert!(needle.len() < 16);
+//! Substring search using the fastest instructions the CPU supports.
+//!
+//! The backend is detected at runtime the first time [`backend`] is called
+//! and cached afterwards, so the same binary runs everywhere and picks up
+//! SSE4.2 or AVX2 where they exist. Other targets use the portable
+//! `core::simd` backend when built with the `portable_simd` feature on
+//! nightly, and word at a time SWAR otherwise.
+//!
+//! Under Miri the SSE4.2 and AVX2 instructions are replaced by scalar
+//! equivalents, so those backends are reported as available and can be
+//! checked there along with the scalar and SWAR ones. The portable backend
+//! only exists when the `portable_simd` feature is enabled.
 
-    unsafe {
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Set of bytes for the byte class scanners.
+///
+/// Sets that fit in 8 ranges are matched with vector range comparisons,
+/// larger ones with a lookup table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ByteSet {
+    table: [bool; 256],
+    ranges: Box<[(u8, u8)]>,
+}
+
+impl ByteSet {
+    pub fn new(bytes: &[u8]) -> Self {
+        let mut table = [false; 256];
+        for &byte in bytes {
+            table[byte as usize] = true;
+        }
+        Self::from_table(table)
+    }
+
+    pub fn from_ranges(ranges: &[RangeInclusive<u8>]) -> Self {
+        let mut table = [false; 256];
+        for range in ranges {
+            for byte in range.clone() {
+                table[byte as usize] = true;
+            }
+        }
+        Self::from_table(table)
+    }
+
+    fn from_table(table: [bool; 256]) -> Self {
+        let mut ranges = Vec::new();
+        let mut start = None;
+
+        for (byte, &member) in table.iter().enumerate() {
+            match (start, member) {
+                (None, true) => start = Some(byte as u8),
+                (Some(lo), false) => {
+                    ranges.push((lo, byte as u8 - 1));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(lo) = start {
+            ranges.push((lo, u8::MAX));
+        }
+
+        Self {
+            table,
+            ranges: ranges.into_boxed_slice(),
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, byte: u8) -> bool {
+        self.table[byte as usize]
+    }
+
+    /// The set as inclusive ranges, when there are few enough for the vector
+    /// range comparisons.
+    #[inline]
+    fn ranges(&self) -> Option<&[(u8, u8)]> {
+        if self.ranges.len() <= 8 {
+            Some(&self.ranges)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// Byte at a time reference implementation.
+    Scalar,
+    /// Eight bytes at a time in `u64` words, available on every target.
+    Swar,
+    /// `core::simd` vectors, needs the nightly `portable_simd` feature.
+    Portable,
+    Sse42,
+    Avx2,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 5] = [
+        Backend::Scalar,
+        Backend::Swar,
+        Backend::Portable,
+        Backend::Sse42,
+        Backend::Avx2,
+    ];
+
+    /// Best backend for the running CPU, without consulting the cache.
+    pub fn detect() -> Self {
+        [Backend::Avx2, Backend::Sse42, Backend::Portable]
+            .iter()
+            .copied()
+            .find(|backend| backend.is_available())
+            .unwrap_or(Backend::Swar)
+    }
+
+    pub fn is_available(self) -> bool {
+        match self {
+            Backend::Scalar | Backend::Swar => true,
+            Backend::Portable => cfg!(feature = "portable_simd"),
+            #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), miri))]
+            Backend::Sse42 | Backend::Avx2 => true,
+            #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(miri)))]
+            Backend::Sse42 => is_x86_feature_detected!("sse4.2"),
+            #[cfg(all(any(target_arch = "x86", target_arch = "x86_64"), not(miri)))]
+            Backend::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+            _ => false,
+        }
+    }
+
+    /// Offset of the first occurrence of `needle` in `haystack`.
+    ///
+    /// The search methods panic if the backend is not available on this CPU.
+    pub fn find(self, haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        self.check();
+
+        match self {
+            Backend::Scalar => scalar::find(haystack, needle),
+            Backend::Swar => swar::find(haystack, needle),
+            #[cfg(feature = "portable_simd")]
+            Backend::Portable => portable::find(haystack, needle),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Sse42 => unsafe { x86::find_sse42(haystack, needle) },
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Avx2 => unsafe { x86::find_avx2(haystack, needle) },
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    /// Offset of the first byte of `haystack` in `set`.
+    pub fn find_any(self, haystack: &[u8], set: &ByteSet) -> Option<usize> {
+        self.check();
+
+        match self {
+            Backend::Scalar => scalar::find_any(haystack, set),
+            Backend::Swar => swar::find_class(haystack, set, false),
+            #[cfg(feature = "portable_simd")]
+            Backend::Portable => portable::find_class(haystack, set, false),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Sse42 => unsafe { x86::find_class_sse42(haystack, set, false) },
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Avx2 => unsafe { x86::find_class_avx2(haystack, set, false) },
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    /// Offset of the first byte of `haystack` not in `set`.
+    pub fn find_not(self, haystack: &[u8], set: &ByteSet) -> Option<usize> {
+        self.check();
+
+        match self {
+            Backend::Scalar => scalar::find_not(haystack, set),
+            Backend::Swar => swar::find_class(haystack, set, true),
+            #[cfg(feature = "portable_simd")]
+            Backend::Portable => portable::find_class(haystack, set, true),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Sse42 => unsafe { x86::find_class_sse42(haystack, set, true) },
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Backend::Avx2 => unsafe { x86::find_class_avx2(haystack, set, true) },
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn check(self) {
+        assert!(
+            self.is_available(),
+            "{:?} backend is not supported by this CPU",
+            self
+        );
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Backend::Scalar => 1,
+            Backend::Swar => 2,
+            Backend::Portable => 3,
+            Backend::Sse42 => 4,
+            Backend::Avx2 => 5,
+        }
+    }
+
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Backend::Scalar),
+            2 => Some(Backend::Swar),
+            3 => Some(Backend::Portable),
+            4 => Some(Backend::Sse42),
+            5 => Some(Backend::Avx2),
+            _ => None,
+        }
+    }
+}
+
+static BACKEND: AtomicU8 = AtomicU8::new(0);
+
+/// Backend used by the SIMD parsers, detected once and cached.
+#[inline]
+pub fn backend() -> Backend {
+    match Backend::from_u8(BACKEND.load(Ordering::Relaxed)) {
+        Some(backend) => backend,
+        None => {
+            let backend = Backend::detect();
+            BACKEND.store(backend.to_u8(), Ordering::Relaxed);
+            backend
+        }
+    }
+}
+
+/// Searches with the cached [`backend`].
+#[inline]
+pub fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    backend().find(haystack, needle)
+}
+
+#[inline]
+pub fn find_any(haystack: &[u8], set: &ByteSet) -> Option<usize> {
+    backend().find_any(haystack, set)
+}
+
+#[inline]
+pub fn find_not(haystack: &[u8], set: &ByteSet) -> Option<usize> {
+    backend().find_not(haystack, set)
+}
+
+mod scalar {
+    use super::ByteSet;
+
+    pub fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        if needle.is_empty() {
+            return Some(0);
+        }
+
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    }
+
+    pub fn find_any(haystack: &[u8], set: &ByteSet) -> Option<usize> {
+        haystack.iter().position(|&byte| set.contains(byte))
+    }
+
+    pub fn find_not(haystack: &[u8], set: &ByteSet) -> Option<usize> {
+        haystack.iter().position(|&byte| !set.contains(byte))
+    }
+}
+
+mod swar {
+    use super::ByteSet;
+    use std::convert::TryInto;
+
+    const LOW: u64 = 0x0101_0101_0101_0101;
+    const HIGH: u64 = 0x8080_8080_8080_8080;
+
+    /// High bit of every byte of `x` that is zero, without false positives.
+    #[inline]
+    fn zero_bytes(x: u64) -> u64 {
+        !(((x & !HIGH) + !HIGH) | x) & HIGH
+    }
+
+    /// Per byte `x - y`, without borrows between bytes.
+    #[inline]
+    fn sub(x: u64, y: u64) -> u64 {
+        ((x | HIGH) - (y & !HIGH)) ^ ((x ^ !y) & HIGH)
+    }
+
+    /// High bit of every byte of `x` that is less than the same byte of `y`.
+    #[inline]
+    fn less(x: u64, y: u64) -> u64 {
+        ((!x & y) | (!(x ^ y) & sub(x, y))) & HIGH
+    }
+
+    #[inline]
+    fn word(bytes: &[u8]) -> u64 {
+        u64::from_le_bytes(bytes.try_into().unwrap())
+    }
+
+    /// First and last byte filter over eight positions per `u64` word.
+    pub fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        if needle.is_empty() {
+            return Some(0);
+        }
+
+        let last = needle.len() - 1;
+        let first_bytes = LOW * needle[0] as u64;
+        let last_bytes = LOW * needle[last] as u64;
+        let mut offset = 0;
+
+        while offset + last + 8 <= haystack.len() {
+            let a = word(&haystack[offset..offset + 8]);
+            let b = word(&haystack[offset + last..offset + last + 8]);
+            let mut mask = zero_bytes(a ^ first_bytes) & zero_bytes(b ^ last_bytes);
+
+            while mask != 0 {
+                let idx = offset + mask.trailing_zeros() as usize / 8;
+                if &haystack[idx..idx + needle.len()] == needle {
+                    return Some(idx);
+                }
+                mask &= mask - 1;
+            }
+
+            offset += 8;
+        }
+
+        super::scalar::find(&haystack[offset..], needle).map(|idx| idx + offset)
+    }
+
+    /// Finds the first byte in `set`, or not in it when `negate` is set.
+    pub fn find_class(haystack: &[u8], set: &ByteSet, negate: bool) -> Option<usize> {
+        let scalar = |haystack| {
+            if negate {
+                super::scalar::find_not(haystack, set)
+            } else {
+                super::scalar::find_any(haystack, set)
+            }
+        };
+        let ranges = match set.ranges() {
+            Some(ranges) => ranges,
+            None => return scalar(haystack),
+        };
+
+        let mut offset = 0;
+
+        while offset + 8 <= haystack.len() {
+            let x = word(&haystack[offset..offset + 8]);
+            // Bytes below `lo` wrap around past `hi - lo`, so one comparison
+            // checks both ends of the range.
+            let mut mask = ranges.iter().fold(0, |mask, &(lo, hi)| {
+                let outside = less(LOW * (hi - lo) as u64, sub(x, LOW * lo as u64));
+                mask | (!outside & HIGH)
+            });
+            if negate {
+                mask ^= HIGH;
+            }
+
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize / 8);
+            }
+
+            offset += 8;
+        }
+
+        scalar(&haystack[offset..]).map(|idx| idx + offset)
+    }
+}
+
+#[cfg(feature = "portable_simd")]
+mod portable {
+    use super::ByteSet;
+    use std::simd::cmp::{SimdPartialEq, SimdPartialOrd};
+    use std::simd::u8x32;
+
+    const LANES: usize = 32;
+
+    /// First and last byte filter on 32 lanes.
+    pub fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        if needle.is_empty() {
+            return Some(0);
+        }
+
+        let last = needle.len() - 1;
+        let first_bytes = u8x32::splat(needle[0]);
+        let last_bytes = u8x32::splat(needle[last]);
+        let mut offset = 0;
+
+        while offset + last + LANES <= haystack.len() {
+            let a = u8x32::from_slice(&haystack[offset..offset + LANES]);
+            let b = u8x32::from_slice(&haystack[offset + last..offset + last + LANES]);
+            let mut mask = (a.simd_eq(first_bytes) & b.simd_eq(last_bytes)).to_bitmask();
+
+            while mask != 0 {
+                let idx = offset + mask.trailing_zeros() as usize;
+                if &haystack[idx..idx + needle.len()] == needle {
+                    return Some(idx);
+                }
+                mask &= mask - 1;
+            }
+
+            offset += LANES;
+        }
+
+        super::scalar::find(&haystack[offset..], needle).map(|idx| idx + offset)
+    }
+
+    /// Finds the first byte in `set`, or not in it when `negate` is set.
+    pub fn find_class(haystack: &[u8], set: &ByteSet, negate: bool) -> Option<usize> {
+        let scalar = |haystack| {
+            if negate {
+                super::scalar::find_not(haystack, set)
+            } else {
+                super::scalar::find_any(haystack, set)
+            }
+        };
+        let ranges = match set.ranges() {
+            Some(ranges) => ranges,
+            None => return scalar(haystack),
+        };
+
+        let mut offset = 0;
+
+        while offset + LANES <= haystack.len() {
+            let block = u8x32::from_slice(&haystack[offset..offset + LANES]);
+            let mut mask = ranges.iter().fold(0, |mask, &(lo, hi)| {
+                let within = block.simd_ge(u8x32::splat(lo)) & block.simd_le(u8x32::splat(hi));
+                mask | within.to_bitmask()
+            });
+            if negate {
+                mask = !mask & ((1 << LANES) - 1);
+            }
+
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+
+            offset += LANES;
+        }
+
+        scalar(&haystack[offset..]).map(|idx| idx + offset)
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    use super::ByteSet;
+    use std::convert::TryInto;
+
+    /// Searches `$lanes` positions at a time, keeping the positions whose
+    /// first and last byte match the needle and verifying those in full.
+    /// Works for needles of any length, the tail is left to the scalar search.
+    macro_rules! filtered {
+        ($haystack:expr, $needle:expr, $lanes:expr, $candidates:path) => {{
+            let (haystack, needle): (&[u8], &[u8]) = ($haystack, $needle);
+
+            if needle.is_empty() {
+                return Some(0);
+            }
+
+            let last = needle.len() - 1;
+            let mut offset = 0;
+
+            while offset + last + $lanes <= haystack.len() {
+                let a = haystack[offset..offset + $lanes].try_into().unwrap();
+                let b = haystack[offset + last..offset + last + $lanes]
+                    .try_into()
+                    .unwrap();
+                let mut mask = $candidates(a, b, needle[0], needle[last]);
+
+                while mask != 0 {
+                    let idx = offset + mask.trailing_zeros() as usize;
+                    if &haystack[idx..idx + needle.len()] == needle {
+                        return Some(idx);
+                    }
+                    mask &= mask - 1;
+                }
+
+                offset += $lanes;
+            }
+
+            super::scalar::find(&haystack[offset..], needle).map(|idx| idx + offset)
+        }};
+    }
+
+    /// Finds `needle` 16 bytes at a time with `pcmpestri`, which compares
+    /// needles of up to 15 bytes, longer ones go through the first and last
+    /// byte filter on 16 lanes instead.
+    ///
+    /// Full blocks are read straight from the haystack and the short tail is
+    /// copied into a zeroed block, so no load goes past the end of either
+    /// slice. A needle that only partially matches at the end of a block is
+    /// retried from the start of the partial match.
+    #[cfg_attr(not(miri), target_feature(enable = "sse4.2"))]
+    pub unsafe fn find_sse42(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        if needle.len() >= 16 {
+            return filtered!(haystack, needle, 16, ops::candidates16);
+        }
+
+        if needle.is_empty() {
+            return Some(0);
+        }
+
+        let mut needle_block = [0; 16];
+        needle_block[..needle.len()].copy_from_slice(needle);
+
+        let mut offset = 0;
+
+        while offset + needle.len() <= haystack.len() {
+            let remaining = haystack.len() - offset;
+            let mut tail = [0; 16];
+            let block: &[u8; 16] = if remaining >= 16 {
+                haystack[offset..offset + 16].try_into().unwrap()
+            } else {
+                tail[..remaining].copy_from_slice(&haystack[offset..]);
+                &tail
+            };
+
+            let block_len = remaining.min(16);
+            let idx = ops::cmpestri_ordered(&needle_block, needle.len(), block, block_len);
+
+            if idx == 16 {
+                offset += 16;
+            } else if idx + needle.len() <= 16 {
+                return Some(offset + idx);
+            } else {
+                offset += idx;
+            }
+        }
+
+        None
+    }
+
+    /// First and last byte filter on 32 lanes, for needles of any length.
+    #[cfg_attr(not(miri), target_feature(enable = "avx2"))]
+    pub unsafe fn find_avx2(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        filtered!(haystack, needle, 32, ops::candidates32)
+    }
+
+    /// Finds the first byte in `set`, or not in it when `negate` is set,
+    /// using `pcmpestri` range comparisons on 16 byte blocks.
+    #[cfg_attr(not(miri), target_feature(enable = "sse4.2"))]
+    pub unsafe fn find_class_sse42(haystack: &[u8], set: &ByteSet, negate: bool) -> Option<usize> {
+        let ranges = match set.ranges() {
+            Some(ranges) => ranges,
+            None => return scalar_class(haystack, set, negate),
+        };
+
+        let mut ranges_block = [0; 16];
+        for (idx, &(lo, hi)) in ranges.iter().enumerate() {
+            ranges_block[idx * 2] = lo;
+            ranges_block[idx * 2 + 1] = hi;
+        }
+
+        let mut offset = 0;
+
+        while offset < haystack.len() {
+            let remaining = haystack.len() - offset;
+            let mut tail = [0; 16];
+            let block: &[u8; 16] = if remaining >= 16 {
+                haystack[offset..offset + 16].try_into().unwrap()
+            } else {
+                tail[..remaining].copy_from_slice(&haystack[offset..]);
+                &tail
+            };
+
+            let block_len = remaining.min(16);
+            let idx =
+                ops::cmpestri_ranges(&ranges_block, ranges.len() * 2, block, block_len, negate);
+
+            if idx < block_len {
+                return Some(offset + idx);
+            }
+
+            offset += 16;
+        }
+
+        None
+    }
+
+    /// Finds the first byte in `set`, or not in it when `negate` is set,
+    /// comparing 32 bytes against each range at a time.
+    #[cfg_attr(not(miri), target_feature(enable = "avx2"))]
+    pub unsafe fn find_class_avx2(haystack: &[u8], set: &ByteSet, negate: bool) -> Option<usize> {
+        let ranges = match set.ranges() {
+            Some(ranges) => ranges,
+            None => return scalar_class(haystack, set, negate),
+        };
+
+        let mut offset = 0;
+
+        while offset + 32 <= haystack.len() {
+            let block = haystack[offset..offset + 32].try_into().unwrap();
+            let mut mask = ops::in_ranges32(block, ranges);
+            if negate {
+                mask = !mask;
+            }
+
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+
+            offset += 32;
+        }
+
+        scalar_class(&haystack[offset..], set, negate).map(|idx| idx + offset)
+    }
+
+    #[inline]
+    fn scalar_class(haystack: &[u8], set: &ByteSet, negate: bool) -> Option<usize> {
+        if negate {
+            super::scalar::find_not(haystack, set)
+        } else {
+            super::scalar::find_any(haystack, set)
+        }
+    }
+
+    #[cfg(not(miri))]
+    mod ops {
         #[cfg(target_arch = "x86")]
         use std::arch::x86::*;
         #[cfg(target_arch = "x86_64")]
         use std::arch::x86_64::*;
 
-        let a = _mm_loadu_si128(needle.as_ptr() as *const _);
-        let len = needle.len() as i32;
-        let chunk_width = 16;
+        /// Index of the first position in `block` where `needle` matches,
+        /// possibly cut off by the end of the block, or 16.
+        #[inline]
+        #[target_feature(enable = "sse4.2")]
+        pub unsafe fn cmpestri_ordered(
+            needle: &[u8; 16],
+            needle_len: usize,
+            block: &[u8; 16],
+            block_len: usize,
+        ) -> usize {
+            let a = _mm_loadu_si128(needle.as_ptr() as *const _);
+            let b = _mm_loadu_si128(block.as_ptr() as *const _);
 
-        for (i, chunk) in haystack.chunks(chunk_width).enumerate() {
-            let b = _mm_loadu_si128(chunk.as_ptr() as *const _);
-            let idx = _mm_cmpestri(a, len, b, chunk_width as i32, _SIDD_CMP_EQUAL_ORDERED);
-            if idx != 16 {
-                return Some(idx as usize + (i * chunk_width));
+            _mm_cmpestri(
+                a,
+                needle_len as i32,
+                b,
+                block_len as i32,
+                _SIDD_CMP_EQUAL_ORDERED,
+            ) as usize
+        }
+
+        /// Index of the first valid byte of `block` inside the ranges, or
+        /// outside them when `negate` is set, or 16.
+        #[inline]
+        #[target_feature(enable = "sse4.2")]
+        pub unsafe fn cmpestri_ranges(
+            ranges: &[u8; 16],
+            ranges_len: usize,
+            block: &[u8; 16],
+            block_len: usize,
+            negate: bool,
+        ) -> usize {
+            let a = _mm_loadu_si128(ranges.as_ptr() as *const _);
+            let b = _mm_loadu_si128(block.as_ptr() as *const _);
+            let (la, lb) = (ranges_len as i32, block_len as i32);
+
+            let idx = if negate {
+                _mm_cmpestri(
+                    a,
+                    la,
+                    b,
+                    lb,
+                    _SIDD_CMP_RANGES | _SIDD_MASKED_NEGATIVE_POLARITY,
+                )
+            } else {
+                _mm_cmpestri(a, la, b, lb, _SIDD_CMP_RANGES)
+            };
+
+            idx as usize
+        }
+
+        /// Bit `i` is set when `block[i]` falls in one of the ranges.
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn in_ranges32(block: &[u8; 32], ranges: &[(u8, u8)]) -> u32 {
+            let x = _mm256_loadu_si256(block.as_ptr() as *const _);
+            let mut acc = _mm256_setzero_si256();
+
+            for &(lo, hi) in ranges {
+                let shifted = _mm256_sub_epi8(x, _mm256_set1_epi8(lo as i8));
+                let span = _mm256_set1_epi8((hi - lo) as i8);
+                let within = _mm256_cmpeq_epi8(_mm256_min_epu8(shifted, span), shifted);
+                acc = _mm256_or_si256(acc, within);
             }
+
+            _mm256_movemask_epi8(acc) as u32
+        }
+
+        /// Bit `i` is set when `a[i]` equals `first` and `b[i]` equals `last`.
+        #[inline]
+        #[target_feature(enable = "sse4.2")]
+        pub unsafe fn candidates16(a: &[u8; 16], b: &[u8; 16], first: u8, last: u8) -> u32 {
+            let a = _mm_loadu_si128(a.as_ptr() as *const _);
+            let b = _mm_loadu_si128(b.as_ptr() as *const _);
+            let eq = _mm_and_si128(
+                _mm_cmpeq_epi8(a, _mm_set1_epi8(first as i8)),
+                _mm_cmpeq_epi8(b, _mm_set1_epi8(last as i8)),
+            );
+
+            _mm_movemask_epi8(eq) as u32
+        }
+
+        #[inline]
+        #[target_feature(enable = "avx2")]
+        pub unsafe fn candidates32(a: &[u8; 32], b: &[u8; 32], first: u8, last: u8) -> u32 {
+            let a = _mm256_loadu_si256(a.as_ptr() as *const _);
+            let b = _mm256_loadu_si256(b.as_ptr() as *const _);
+            let eq = _mm256_and_si256(
+                _mm256_cmpeq_epi8(a, _mm256_set1_epi8(first as i8)),
+                _mm256_cmpeq_epi8(b, _mm256_set1_epi8(last as i8)),
+            );
+
+            _mm256_movemask_epi8(eq) as u32
         }
     }
 
-    None
+    /// Scalar stand-ins for the intrinsics, Miri cannot execute them but can
+    /// still check the bounds handling of the kernels around them.
+    #[cfg(miri)]
+    mod ops {
+        pub unsafe fn cmpestri_ordered(
+            needle: &[u8; 16],
+            needle_len: usize,
+            block: &[u8; 16],
+            block_len: usize,
+        ) -> usize {
+            (0..16)
+                .find(|&i| {
+                    (0..needle_len)
+                        .take_while(|&j| i + j < 16)
+                        .all(|j| i + j < block_len && block[i + j] == needle[j])
+                })
+                .unwrap_or(16)
+        }
+
+        pub unsafe fn cmpestri_ranges(
+            ranges: &[u8; 16],
+            ranges_len: usize,
+            block: &[u8; 16],
+            block_len: usize,
+            negate: bool,
+        ) -> usize {
+            let pairs = &ranges[..ranges_len];
+
+            (0..block_len)
+                .find(|&i| {
+                    let inside = pairs
+                        .chunks(2)
+                        .any(|pair| pair[0] <= block[i] && block[i] <= pair[1]);
+                    inside != negate
+                })
+                .unwrap_or(16)
+        }
+
+        pub unsafe fn in_ranges32(block: &[u8; 32], ranges: &[(u8, u8)]) -> u32 {
+            (0..32)
+                .filter(|&i| {
+                    ranges
+                        .iter()
+                        .any(|&(lo, hi)| lo <= block[i] && block[i] <= hi)
+                })
+                .fold(0, |mask, i| mask | 1 << i)
+        }
+
+        pub unsafe fn candidates16(a: &[u8; 16], b: &[u8; 16], first: u8, last: u8) -> u32 {
+            (0..16)
+                .filter(|&i| a[i] == first && b[i] == last)
+                .fold(0, |mask, i| mask | 1 << i)
+        }
+
+        pub unsafe fn candidates32(a: &[u8; 32], b: &[u8; 32], first: u8, last: u8) -> u32 {
+            (0..32)
+                .filter(|&i| a[i] == first && b[i] == last)
+                .fold(0, |mask, i| mask | 1 << i)
+        }
+    }
 }
@@ -0,0 +1,151 @@
+use crate::report::Location;
+use crate::stream::{AsBytes, Decode, Input, Text};
+use std::ops::Range;
+
+/// Half open range of offsets into the original input.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    #[inline]
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Line and column of the start of the span within `source`.
+    pub fn location(&self, source: &[u8]) -> Location {
+        Location::new(source, self.start)
+    }
+}
+
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Self {
+        span.start..span.end
+    }
+}
+
+/// Input wrapper that keeps track of its absolute offset as it is consumed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Located<I> {
+    input: I,
+    offset: usize,
+}
+
+impl<I> Located<I> {
+    #[inline]
+    pub fn new(input: I) -> Self {
+        Self { input, offset: 0 }
+    }
+
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    #[inline]
+    pub fn fragment(&self) -> &I {
+        &self.input
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> I {
+        self.input
+    }
+}
+
+impl<I: Input> Located<I> {
+    /// Span covered by this fragment of the input.
+    #[inline]
+    pub fn span(&self) -> Span {
+        Span::new(self.offset, self.offset + self.input.input_len())
+    }
+
+    #[inline]
+    fn advance(&self, next: I) -> Self {
+        Self {
+            input: next,
+            offset: self.offset + self.input.input_len() - next.input_len(),
+        }
+    }
+}
+
+impl<I: Input> Input for Located<I> {
+    type Item = I::Item;
+
+    #[inline]
+    fn input_len(&self) -> usize {
+        self.input.input_len()
+    }
+
+    #[inline]
+    fn next_item(&self) -> Option<(Self::Item, Self)> {
+        let (item, next) = self.input.next_item()?;
+        Some((item, self.advance(next)))
+    }
+
+    #[inline]
+    fn split_at(&self, offset: usize) -> (Self, Self) {
+        let (head, tail) = self.input.split_at(offset);
+        let head = Self {
+            input: head,
+            offset: self.offset,
+        };
+
+        (head, self.advance(tail))
+    }
+
+    #[inline]
+    fn split_at_checked(&self, offset: usize) -> Option<(Self, Self)> {
+        let (head, tail) = self.input.split_at_checked(offset)?;
+        let head = Self {
+            input: head,
+            offset: self.offset,
+        };
+
+        Some((head, self.advance(tail)))
+    }
+}
+
+impl<I: AsBytes> AsBytes for Located<I> {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self.input.as_bytes()
+    }
+}
+
+impl<I: Text> Text for Located<I> {
+    #[inline]
+    fn next_char(&self) -> Decode<Self> {
+        match self.input.next_char() {
+            Decode::Char(ch, next) => Decode::Char(ch, self.advance(next)),
+            Decode::Invalid => Decode::Invalid,
+            Decode::Truncated(needed) => Decode::Truncated(needed),
+            Decode::Empty => Decode::Empty,
+        }
+    }
+}
+
+/// Inputs that know their absolute offset.
+pub trait Locate: Input {
+    fn location_offset(&self) -> usize;
+}
+
+impl<I: Input> Locate for Located<I> {
+    #[inline]
+    fn location_offset(&self) -> usize {
+        self.offset
+    }
+}
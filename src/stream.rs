@@ -1,3 +1,6 @@
+use crate::error::Found;
+use std::fmt;
+
 pub trait Stream<'a> {
     fn to_stream(&'a self) -> &'a [u8];
 }
@@ -22,3 +25,256 @@ impl<'a> Stream<'a> for Vec<u8> {
         self.as_slice()
     }
 }
+
+impl<'a, const N: usize> Stream<'a> for [u8; N] {
+    #[inline]
+    fn to_stream(&'a self) -> &'a [u8] {
+        self
+    }
+}
+
+/// Input consumed by parsers.
+///
+/// Lengths and offsets are measured in the units the input is indexed by,
+/// bytes for `&[u8]` and `&str`, tokens for `&[T]`.
+pub trait Input: Copy {
+    type Item: Token + Copy;
+
+    fn input_len(&self) -> usize;
+
+    /// Splits off the next item, stepping a whole `char` for `&str`.
+    fn next_item(&self) -> Option<(Self::Item, Self)>;
+
+    /// Splits at `offset`, which must lie on an item boundary.
+    fn split_at(&self, offset: usize) -> (Self, Self);
+
+    /// Splits at `offset`, or returns `None` if it is past the end or not on
+    /// an item boundary, such as inside a multi-byte `char` of a `&str`.
+    fn split_at_checked(&self, offset: usize) -> Option<(Self, Self)>;
+}
+
+impl<'a, T: Token> Input for &'a [T] {
+    type Item = &'a T;
+
+    #[inline]
+    fn input_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn next_item(&self) -> Option<(Self::Item, Self)> {
+        self.split_first()
+    }
+
+    #[inline]
+    fn split_at(&self, offset: usize) -> (Self, Self) {
+        <[T]>::split_at(self, offset)
+    }
+
+    #[inline]
+    fn split_at_checked(&self, offset: usize) -> Option<(Self, Self)> {
+        <[T]>::split_at_checked(self, offset)
+    }
+}
+
+impl Input for &str {
+    type Item = char;
+
+    #[inline]
+    fn input_len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn next_item(&self) -> Option<(Self::Item, Self)> {
+        let ch = self.chars().next()?;
+        Some((ch, &self[ch.len_utf8()..]))
+    }
+
+    #[inline]
+    fn split_at(&self, offset: usize) -> (Self, Self) {
+        str::split_at(self, offset)
+    }
+
+    #[inline]
+    fn split_at_checked(&self, offset: usize) -> Option<(Self, Self)> {
+        str::split_at_checked(self, offset)
+    }
+}
+
+/// Values that can be passed to [`Cell::parse`] in place of an input, so
+/// byte string literals and borrowed buffers work without converting them.
+///
+/// [`Cell::parse`]: crate::cell::Cell::parse
+pub trait IntoInput {
+    type Input: Input;
+
+    fn into_input(self) -> Self::Input;
+}
+
+impl<I: Input> IntoInput for I {
+    type Input = I;
+
+    #[inline]
+    fn into_input(self) -> Self::Input {
+        self
+    }
+}
+
+impl<'a, T: Token, const N: usize> IntoInput for &'a [T; N] {
+    type Input = &'a [T];
+
+    #[inline]
+    fn into_input(self) -> Self::Input {
+        self
+    }
+}
+
+impl<'a, T: Token> IntoInput for &'a Vec<T> {
+    type Input = &'a [T];
+
+    #[inline]
+    fn into_input(self) -> Self::Input {
+        self
+    }
+}
+
+impl<'a, T: Token> IntoInput for &&'a [T] {
+    type Input = &'a [T];
+
+    #[inline]
+    fn into_input(self) -> Self::Input {
+        *self
+    }
+}
+
+impl<'a> IntoInput for &&'a str {
+    type Input = &'a str;
+
+    #[inline]
+    fn into_input(self) -> Self::Input {
+        *self
+    }
+}
+
+impl<'a> IntoInput for &'a String {
+    type Input = &'a str;
+
+    #[inline]
+    fn into_input(self) -> Self::Input {
+        self
+    }
+}
+
+/// Inputs that can be viewed as bytes, for the literal matching parsers.
+pub trait AsBytes {
+    fn as_bytes(&self) -> &[u8];
+}
+
+impl AsBytes for &[u8] {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+}
+
+impl AsBytes for &str {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+}
+
+/// Result of decoding the next `char` of a [`Text`] input.
+pub enum Decode<I> {
+    Char(char, I),
+    Invalid,
+    /// The input ends inside a multi-byte sequence missing this many bytes.
+    Truncated(usize),
+    Empty,
+}
+
+/// Inputs that can be read as UTF-8 text.
+pub trait Text: Input {
+    fn next_char(&self) -> Decode<Self>;
+}
+
+impl Text for &[u8] {
+    fn next_char(&self) -> Decode<Self> {
+        let first = match self.first() {
+            Some(&first) => first,
+            None => return Decode::Empty,
+        };
+
+        if first < 0x80 {
+            return Decode::Char(first as char, &self[1..]);
+        }
+
+        let width = match first {
+            0xc2..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf4 => 4,
+            _ => return Decode::Invalid,
+        };
+
+        match std::str::from_utf8(&self[..width.min(self.len())]) {
+            Ok(s) => {
+                let ch = s.chars().next().unwrap();
+                Decode::Char(ch, &self[width..])
+            }
+            Err(err) if err.error_len().is_none() => Decode::Truncated(width - self.len()),
+            Err(_) => Decode::Invalid,
+        }
+    }
+}
+
+impl Text for &str {
+    #[inline]
+    fn next_char(&self) -> Decode<Self> {
+        match self.next_item() {
+            Some((ch, next)) => Decode::Char(ch, next),
+            None => Decode::Empty,
+        }
+    }
+}
+
+impl Text for &[char] {
+    #[inline]
+    fn next_char(&self) -> Decode<Self> {
+        match self.split_first() {
+            Some((&ch, next)) => Decode::Char(ch, next),
+            None => Decode::Empty,
+        }
+    }
+}
+
+/// Items of an input, describes itself for error messages.
+///
+/// Lexer tokens only need `impl Token for MyToken {}`, which reports them
+/// through their `Debug` representation.
+pub trait Token: fmt::Debug {
+    fn found(&self) -> Found {
+        Found::Token(format!("{:?}", self))
+    }
+}
+
+impl Token for u8 {
+    #[inline]
+    fn found(&self) -> Found {
+        Found::Byte(*self)
+    }
+}
+
+impl Token for char {
+    #[inline]
+    fn found(&self) -> Found {
+        Found::Char(*self)
+    }
+}
+
+impl<T: Token + ?Sized> Token for &T {
+    #[inline]
+    fn found(&self) -> Found {
+        (**self).found()
+    }
+}
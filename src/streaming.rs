@@ -0,0 +1,234 @@
+//! Parsers for partial input.
+//!
+//! These behave like their counterparts in [`crate::parser`] except that
+//! running out of input is reported as an incomplete [`ParseError`] rather
+//! than a failure. Sequencing and repetition combinators work in either mode.
+
+use crate::cell::Cell;
+use crate::error::{Expected, Needed, ParseError};
+use crate::parser::Parse;
+use crate::stream::{AsBytes, Decode, Input, Text};
+
+pub struct Byte {
+    byte: u8,
+}
+
+impl Byte {
+    #[inline]
+    pub fn new(byte: u8) -> Self {
+        Self { byte }
+    }
+}
+
+impl<'a, I> Parse<'a, I> for Byte
+where
+    I: Input<Item = &'a u8>,
+{
+    type Output = &'a u8;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        match input.next_item() {
+            Some((byte, next)) if *byte == self.byte => Ok((next, byte)),
+            Some(_) => Err(ParseError::new(input, Expected::Byte(self.byte))),
+            None => Err(ParseError::incomplete(
+                input,
+                Needed::Size(1),
+                Expected::Byte(self.byte),
+            )),
+        }
+    }
+}
+
+pub struct Char {
+    ch: char,
+}
+
+impl Char {
+    #[inline]
+    pub fn new(ch: char) -> Self {
+        Self { ch }
+    }
+}
+
+impl<'a, I> Parse<'a, I> for Char
+where
+    I: Text,
+{
+    type Output = char;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let needed = match input.next_char() {
+            Decode::Char(ch, next) if ch == self.ch => return Ok((next, ch)),
+            Decode::Char(..) | Decode::Invalid => {
+                return Err(ParseError::new(input, Expected::Char(self.ch)))
+            }
+            Decode::Truncated(needed) => needed,
+            Decode::Empty => 1,
+        };
+
+        Err(ParseError::incomplete(
+            input,
+            Needed::Size(needed),
+            Expected::Char(self.ch),
+        ))
+    }
+}
+
+pub struct Slice {
+    bytes: Box<[u8]>,
+}
+
+impl Slice {
+    pub fn new(slice: &[u8]) -> Self {
+        Self {
+            bytes: slice.into(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+}
+
+impl<'a, I> Parse<'a, I> for Slice
+where
+    I: Input + AsBytes,
+{
+    type Output = I;
+
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let bytes = input.as_bytes();
+
+        if bytes.len() < self.len() && self.bytes.starts_with(bytes) {
+            return Err(ParseError::incomplete(
+                input,
+                Needed::Size(self.len() - bytes.len()),
+                Expected::Slice(self.bytes.clone()),
+            ));
+        }
+
+        match input.split_at_checked(self.len()) {
+            Some((output, next)) if output.as_bytes() == &self.bytes[..] => Ok((next, output)),
+            _ => Err(ParseError::new(input, Expected::Slice(self.bytes.clone()))),
+        }
+    }
+}
+
+/// Converts incomplete errors from `parser` into regular failures, for
+/// parsing the final buffer of a stream.
+pub struct Complete<P> {
+    parser: P,
+}
+
+impl<P> Complete<P> {
+    #[inline]
+    pub fn new(parser: P) -> Self {
+        Self { parser }
+    }
+}
+
+impl<'a, I, P> Parse<'a, I> for Complete<P>
+where
+    P: Parse<'a, I>,
+{
+    type Output = P::Output;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        self.parser.parse(input).map_err(ParseError::into_complete)
+    }
+}
+
+#[inline]
+pub fn byte<'a, I>(byte: u8) -> Cell<'a, Byte, I>
+where
+    Byte: Parse<'a, I>,
+{
+    Cell::new(Byte::new(byte))
+}
+
+#[inline]
+pub fn chr<'a, I>(ch: char) -> Cell<'a, Char, I>
+where
+    Char: Parse<'a, I>,
+{
+    Cell::new(Char::new(ch))
+}
+
+#[inline]
+pub fn slice<'a, I>(bytes: &[u8]) -> Cell<'a, Slice, I>
+where
+    Slice: Parse<'a, I>,
+{
+    Cell::new(Slice::new(bytes))
+}
+
+#[inline]
+pub fn complete<'a, P, I>(parser: P) -> Cell<'a, Complete<P>, I>
+where
+    P: Parse<'a, I>,
+{
+    Cell::new(Complete::new(parser))
+}
+
+pub mod simd {
+    use super::*;
+    use crate::simd::{self, Backend};
+
+    pub struct TakeUntilLiteral {
+        bytes: Box<[u8]>,
+        backend: Backend,
+    }
+
+    impl TakeUntilLiteral {
+        pub fn new(bytes: &[u8]) -> Self {
+            Self::with_backend(bytes, simd::backend())
+        }
+
+        pub fn with_backend(bytes: &[u8], backend: Backend) -> Self {
+            assert!(backend.is_available());
+            Self {
+                bytes: bytes.to_vec().into_boxed_slice(),
+                backend,
+            }
+        }
+    }
+
+    impl<'a, I> Parse<'a, I> for TakeUntilLiteral
+    where
+        I: Input + AsBytes,
+    {
+        type Output = I;
+
+        fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+            let found = self.backend.find(input.as_bytes(), &self.bytes);
+            let end = input.split_at(input.input_len()).1;
+
+            match found.map(|idx| input.split_at_checked(idx)) {
+                Some(Some((output, next))) => Ok((next, output)),
+                // Found inside a multi-byte `char` of a `&str`, the literal
+                // starts with a continuation byte and more input cannot help.
+                Some(None) => Err(ParseError::new(end, Expected::Slice(self.bytes.clone()))),
+                None => Err(ParseError::incomplete(
+                    end,
+                    Needed::Unknown,
+                    Expected::Slice(self.bytes.clone()),
+                )),
+            }
+        }
+    }
+
+    #[inline]
+    pub fn take_until_literal<'a, I>(bytes: &[u8]) -> Cell<'a, TakeUntilLiteral, I>
+    where
+        TakeUntilLiteral: Parse<'a, I>,
+    {
+        Cell::new(TakeUntilLiteral::new(bytes))
+    }
+}
@@ -0,0 +1,210 @@
+//! Combinators over tuples of parsers.
+//!
+//! [`seq`] runs its parsers in order and returns their outputs as one flat
+//! tuple, [`alt`] returns the output of the first parser that succeeds and
+//! [`permutation`] accepts its parsers in any order. Tuples of up to 12
+//! parsers are supported.
+//!
+//! ```
+//! use parsing::prelude::*;
+//!
+//! let word = |bytes: &'static [u8]| slice(bytes).map(|word| std::str::from_utf8(word).unwrap());
+//! let method = alt((word(b"GET"), word(b"HEAD"), word(b"POST")));
+//! let line = seq((method, chr(' '), take_until(chr(' ')), chr(' '), slice(b"HTTP/1.1")));
+//!
+//! let (_, (method, _, path, _, _)) = line.parse(b"HEAD /index.html HTTP/1.1".to_stream()).unwrap();
+//! assert_eq!((method, path), ("HEAD", &b"/index.html"[..]));
+//! ```
+
+use crate::cell::Cell;
+use crate::error::ParseError;
+use crate::parser::Parse;
+
+/// Runs every parser in order, the output is the tuple of their outputs.
+pub struct Seq<T> {
+    parsers: T,
+}
+
+impl<T> Seq<T> {
+    #[inline]
+    pub fn new(parsers: T) -> Self {
+        Self { parsers }
+    }
+}
+
+/// Tries every parser in order on the same input and returns the first
+/// success. All parsers must have the same output.
+pub struct Alt<T> {
+    parsers: T,
+}
+
+impl<T> Alt<T> {
+    #[inline]
+    pub fn new(parsers: T) -> Self {
+        Self { parsers }
+    }
+}
+
+/// Runs every parser exactly once, in whatever order they match. Outputs are
+/// returned in the order of the tuple.
+///
+/// At each position the parsers that have not matched yet are tried in
+/// order, so a parser that also matches the input of a later one should come
+/// after it.
+pub struct Permutation<T> {
+    parsers: T,
+}
+
+impl<T> Permutation<T> {
+    #[inline]
+    pub fn new(parsers: T) -> Self {
+        Self { parsers }
+    }
+}
+
+macro_rules! tuple_impls {
+    ($P1:ident 0 $(, $P:ident $idx:tt)*) => {
+        impl<'a, I, $P1, $($P),*> Parse<'a, I> for Seq<($P1, $($P,)*)>
+        where
+            $P1: Parse<'a, I>,
+            $($P: Parse<'a, I>,)*
+        {
+            type Output = ($P1::Output, $($P::Output,)*);
+
+            #[inline]
+            #[allow(non_snake_case)]
+            fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+                let (input, $P1) = self.parsers.0.parse(input)?;
+                $(let (input, $P) = self.parsers.$idx.parse(input)?;)*
+
+                Ok((input, ($P1, $($P,)*)))
+            }
+        }
+
+        impl<'a, I, $P1, $($P),*> Parse<'a, I> for Alt<($P1, $($P,)*)>
+        where
+            I: Copy,
+            $P1: Parse<'a, I>,
+            $($P: Parse<'a, I, Output = $P1::Output>,)*
+        {
+            type Output = $P1::Output;
+
+            #[inline]
+            fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+                #[allow(unused_mut)]
+                let mut err = match self.parsers.0.parse(input) {
+                    Ok(res) => return Ok(res),
+                    Err(err) => err,
+                };
+
+                $(
+                    if !err.is_recoverable() {
+                        return Err(err);
+                    }
+
+                    err = match self.parsers.$idx.parse(input) {
+                        Ok(res) => return Ok(res),
+                        Err(next) => err.merge(next),
+                    };
+                )*
+
+                Err(err)
+            }
+        }
+
+        impl<'a, I, $P1, $($P),*> Parse<'a, I> for Permutation<($P1, $($P,)*)>
+        where
+            I: Copy,
+            $P1: Parse<'a, I>,
+            $($P: Parse<'a, I>,)*
+        {
+            type Output = ($P1::Output, $($P::Output,)*);
+
+            #[inline]
+            fn parse(&self, mut input: I) -> Result<(I, Self::Output), ParseError> {
+                let mut outputs: (Option<$P1::Output>, $(Option<$P::Output>,)*) =
+                    (None, $(None::<$P::Output>,)*);
+
+                while outputs.0.is_none() $(|| outputs.$idx.is_none())* {
+                    let mut err: Option<ParseError> = None;
+
+                    tuple_impls!(@try self, input, outputs, err, 0 $(, $idx)*);
+
+                    if let Some(err) = err {
+                        return Err(err);
+                    }
+                }
+
+                Ok((input, (outputs.0.unwrap(), $(outputs.$idx.unwrap(),)*)))
+            }
+        }
+    };
+
+    // Tries the parsers that have not matched yet, stopping at the first
+    // one that does. `err` is left as `None` after a match.
+    (@try $self:ident, $input:ident, $outputs:ident, $err:ident, $($idx:tt),+) => {
+        'matched: {
+            $(
+                if $outputs.$idx.is_none() {
+                    match $self.parsers.$idx.parse($input) {
+                        Ok((next, output)) => {
+                            $outputs.$idx = Some(output);
+                            $input = next;
+                            $err = None;
+                            break 'matched;
+                        }
+                        Err(next) => {
+                            let merged = match $err.take() {
+                                Some(err) => err.merge(next),
+                                None => next,
+                            };
+
+                            if !merged.is_recoverable() {
+                                return Err(merged);
+                            }
+
+                            $err = Some(merged);
+                        }
+                    }
+                }
+            )+
+        }
+    };
+}
+
+tuple_impls!(P1 0);
+tuple_impls!(P1 0, P2 1);
+tuple_impls!(P1 0, P2 1, P3 2);
+tuple_impls!(P1 0, P2 1, P3 2, P4 3);
+tuple_impls!(P1 0, P2 1, P3 2, P4 3, P5 4);
+tuple_impls!(P1 0, P2 1, P3 2, P4 3, P5 4, P6 5);
+tuple_impls!(P1 0, P2 1, P3 2, P4 3, P5 4, P6 5, P7 6);
+tuple_impls!(P1 0, P2 1, P3 2, P4 3, P5 4, P6 5, P7 6, P8 7);
+tuple_impls!(P1 0, P2 1, P3 2, P4 3, P5 4, P6 5, P7 6, P8 7, P9 8);
+tuple_impls!(P1 0, P2 1, P3 2, P4 3, P5 4, P6 5, P7 6, P8 7, P9 8, P10 9);
+tuple_impls!(P1 0, P2 1, P3 2, P4 3, P5 4, P6 5, P7 6, P8 7, P9 8, P10 9, P11 10);
+tuple_impls!(P1 0, P2 1, P3 2, P4 3, P5 4, P6 5, P7 6, P8 7, P9 8, P10 9, P11 10, P12 11);
+
+#[inline]
+pub fn seq<'a, T, I>(parsers: T) -> Cell<'a, Seq<T>, I>
+where
+    Seq<T>: Parse<'a, I>,
+{
+    Cell::new(Seq::new(parsers))
+}
+
+#[inline]
+pub fn alt<'a, T, I>(parsers: T) -> Cell<'a, Alt<T>, I>
+where
+    Alt<T>: Parse<'a, I>,
+{
+    Cell::new(Alt::new(parsers))
+}
+
+#[inline]
+pub fn permutation<'a, T, I>(parsers: T) -> Cell<'a, Permutation<T>, I>
+where
+    Permutation<T>: Parse<'a, I>,
+{
+    Cell::new(Permutation::new(parsers))
+}
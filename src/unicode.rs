@@ -0,0 +1,426 @@
+//! Unicode aware character parsers.
+//!
+//! Each parser decodes one `char` from a [`Text`] input, so byte input is
+//! read as UTF-8 and advanced by the encoded length. Invalid or truncated
+//! sequences are rejected with the parser's expectation.
+
+mod tables;
+
+use crate::cell::Cell;
+use crate::error::{Expected, ParseError};
+use crate::parser::Parse;
+use crate::stream::{Decode, Text};
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Unicode general category of a code point, as assigned by the Unicode
+/// 14.0 character database.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GeneralCategory {
+    /// Uppercase letter.
+    Lu,
+    /// Lowercase letter.
+    Ll,
+    /// Titlecase letter.
+    Lt,
+    /// Modifier letter.
+    Lm,
+    /// Other letter.
+    Lo,
+    /// Nonspacing mark.
+    Mn,
+    /// Spacing mark.
+    Mc,
+    /// Enclosing mark.
+    Me,
+    /// Decimal number.
+    Nd,
+    /// Letter number.
+    Nl,
+    /// Other number.
+    No,
+    /// Connector punctuation.
+    Pc,
+    /// Dash punctuation.
+    Pd,
+    /// Open punctuation.
+    Ps,
+    /// Close punctuation.
+    Pe,
+    /// Initial quote punctuation.
+    Pi,
+    /// Final quote punctuation.
+    Pf,
+    /// Other punctuation.
+    Po,
+    /// Math symbol.
+    Sm,
+    /// Currency symbol.
+    Sc,
+    /// Modifier symbol.
+    Sk,
+    /// Other symbol.
+    So,
+    /// Space separator.
+    Zs,
+    /// Line separator.
+    Zl,
+    /// Paragraph separator.
+    Zp,
+    /// Control character.
+    Cc,
+    /// Format character.
+    Cf,
+    /// Surrogate, never assigned to a `char`.
+    Cs,
+    /// Private use character.
+    Co,
+    /// Unassigned code point.
+    Cn,
+}
+
+impl GeneralCategory {
+    /// Looks up the general category of `ch`.
+    pub fn of(ch: char) -> Self {
+        let table = tables::GENERAL_CATEGORY;
+        let idx = table.partition_point(|&(start, _)| start <= ch as u32);
+
+        // The first run starts at U+0000, so `idx` is at least 1.
+        table[idx - 1].1
+    }
+
+    /// Two letter abbreviation used by the Unicode standard, such as `Lu`.
+    pub fn abbr(self) -> &'static str {
+        use GeneralCategory::*;
+
+        match self {
+            Lu => "Lu",
+            Ll => "Ll",
+            Lt => "Lt",
+            Lm => "Lm",
+            Lo => "Lo",
+            Mn => "Mn",
+            Mc => "Mc",
+            Me => "Me",
+            Nd => "Nd",
+            Nl => "Nl",
+            No => "No",
+            Pc => "Pc",
+            Pd => "Pd",
+            Ps => "Ps",
+            Pe => "Pe",
+            Pi => "Pi",
+            Pf => "Pf",
+            Po => "Po",
+            Sm => "Sm",
+            Sc => "Sc",
+            Sk => "Sk",
+            So => "So",
+            Zs => "Zs",
+            Zl => "Zl",
+            Zp => "Zp",
+            Cc => "Cc",
+            Cf => "Cf",
+            Cs => "Cs",
+            Co => "Co",
+            Cn => "Cn",
+        }
+    }
+}
+
+/// Character classes matched by [`Class`].
+///
+/// The first variants are the binary properties `char` exposes, while
+/// [`Category::General`] matches a single Unicode general category.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Category {
+    Alphabetic,
+    Numeric,
+    Alphanumeric,
+    Whitespace,
+    Uppercase,
+    Lowercase,
+    Control,
+    General(GeneralCategory),
+}
+
+impl Category {
+    pub fn contains(self, ch: char) -> bool {
+        match self {
+            Category::Alphabetic => ch.is_alphabetic(),
+            Category::Numeric => ch.is_numeric(),
+            Category::Alphanumeric => ch.is_alphanumeric(),
+            Category::Whitespace => ch.is_whitespace(),
+            Category::Uppercase => ch.is_uppercase(),
+            Category::Lowercase => ch.is_lowercase(),
+            Category::Control => ch.is_control(),
+            Category::General(general) => GeneralCategory::of(ch) == general,
+        }
+    }
+}
+
+impl From<GeneralCategory> for Category {
+    fn from(general: GeneralCategory) -> Self {
+        Category::General(general)
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Category::Alphabetic => write!(f, "alphabetic character"),
+            Category::Numeric => write!(f, "numeric character"),
+            Category::Alphanumeric => write!(f, "alphanumeric character"),
+            Category::Whitespace => write!(f, "whitespace"),
+            Category::Uppercase => write!(f, "uppercase character"),
+            Category::Lowercase => write!(f, "lowercase character"),
+            Category::Control => write!(f, "control character"),
+            Category::General(general) => write!(f, "character in category {}", general.abbr()),
+        }
+    }
+}
+
+/// Decodes the next `char` of `input` in complete mode.
+#[inline]
+pub(crate) fn next_char<I: Text>(input: I, expected: Expected) -> Result<(char, I), ParseError> {
+    match input.next_char() {
+        Decode::Char(ch, next) => Ok((ch, next)),
+        _ => Err(ParseError::new(input, expected)),
+    }
+}
+
+/// Any Unicode scalar value.
+pub struct Scalar;
+
+impl Scalar {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Default for Scalar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, I> Parse<'a, I> for Scalar
+where
+    I: Text,
+{
+    type Output = char;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let (ch, next) = next_char(input, Expected::Scalar)?;
+        Ok((next, ch))
+    }
+}
+
+pub struct Class {
+    category: Category,
+}
+
+impl Class {
+    pub fn new(category: Category) -> Self {
+        Self { category }
+    }
+}
+
+impl<'a, I> Parse<'a, I> for Class
+where
+    I: Text,
+{
+    type Output = char;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let expected = Expected::Category(self.category);
+
+        match next_char(input, expected.clone())? {
+            (ch, next) if self.category.contains(ch) => Ok((next, ch)),
+            _ => Err(ParseError::new(input, expected)),
+        }
+    }
+}
+
+pub struct CharRange {
+    start: char,
+    end: char,
+}
+
+impl CharRange {
+    pub fn new(range: RangeInclusive<char>) -> Self {
+        Self {
+            start: *range.start(),
+            end: *range.end(),
+        }
+    }
+}
+
+impl<'a, I> Parse<'a, I> for CharRange
+where
+    I: Text,
+{
+    type Output = char;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let expected = Expected::Range(self.start, self.end);
+
+        match next_char(input, expected.clone())? {
+            (ch, next) if (self.start..=self.end).contains(&ch) => Ok((next, ch)),
+            _ => Err(ParseError::new(input, expected)),
+        }
+    }
+}
+
+pub struct OneOf {
+    chars: Box<[char]>,
+}
+
+impl OneOf {
+    pub fn new(chars: &str) -> Self {
+        Self {
+            chars: chars.chars().collect(),
+        }
+    }
+
+    fn error<I: Text>(&self, input: I) -> ParseError {
+        let mut chars = self.chars.iter();
+        let mut err = match chars.next() {
+            Some(&ch) => ParseError::new(input, Expected::Char(ch)),
+            None => ParseError::unexpected(input),
+        };
+
+        for &ch in chars {
+            err = err.merge(ParseError::new(input, Expected::Char(ch)));
+        }
+
+        err
+    }
+}
+
+impl<'a, I> Parse<'a, I> for OneOf
+where
+    I: Text,
+{
+    type Output = char;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        match input.next_char() {
+            Decode::Char(ch, next) if self.chars.contains(&ch) => Ok((next, ch)),
+            _ => Err(self.error(input)),
+        }
+    }
+}
+
+pub struct NoneOf {
+    chars: Box<[char]>,
+}
+
+impl NoneOf {
+    pub fn new(chars: &str) -> Self {
+        Self {
+            chars: chars.chars().collect(),
+        }
+    }
+}
+
+impl<'a, I> Parse<'a, I> for NoneOf
+where
+    I: Text,
+{
+    type Output = char;
+
+    #[inline]
+    fn parse(&self, input: I) -> Result<(I, Self::Output), ParseError> {
+        let expected = Expected::NoneOf(self.chars.clone());
+
+        match next_char(input, expected.clone())? {
+            (ch, next) if !self.chars.contains(&ch) => Ok((next, ch)),
+            _ => Err(ParseError::new(input, expected)),
+        }
+    }
+}
+
+#[inline]
+pub fn any_scalar<'a, I>() -> Cell<'a, Scalar, I>
+where
+    Scalar: Parse<'a, I>,
+{
+    Cell::new(Scalar::new())
+}
+
+#[inline]
+pub fn category<'a, I>(category: Category) -> Cell<'a, Class, I>
+where
+    Class: Parse<'a, I>,
+{
+    Cell::new(Class::new(category))
+}
+
+#[inline]
+pub fn general_category<'a, I>(general: GeneralCategory) -> Cell<'a, Class, I>
+where
+    Class: Parse<'a, I>,
+{
+    category(Category::General(general))
+}
+
+#[inline]
+pub fn alphabetic<'a, I>() -> Cell<'a, Class, I>
+where
+    Class: Parse<'a, I>,
+{
+    category(Category::Alphabetic)
+}
+
+#[inline]
+pub fn numeric<'a, I>() -> Cell<'a, Class, I>
+where
+    Class: Parse<'a, I>,
+{
+    category(Category::Numeric)
+}
+
+#[inline]
+pub fn alphanumeric<'a, I>() -> Cell<'a, Class, I>
+where
+    Class: Parse<'a, I>,
+{
+    category(Category::Alphanumeric)
+}
+
+#[inline]
+pub fn whitespace<'a, I>() -> Cell<'a, Class, I>
+where
+    Class: Parse<'a, I>,
+{
+    category(Category::Whitespace)
+}
+
+#[inline]
+pub fn char_range<'a, I>(range: RangeInclusive<char>) -> Cell<'a, CharRange, I>
+where
+    CharRange: Parse<'a, I>,
+{
+    Cell::new(CharRange::new(range))
+}
+
+#[inline]
+pub fn one_of<'a, I>(chars: &str) -> Cell<'a, OneOf, I>
+where
+    OneOf: Parse<'a, I>,
+{
+    Cell::new(OneOf::new(chars))
+}
+
+#[inline]
+pub fn none_of<'a, I>(chars: &str) -> Cell<'a, NoneOf, I>
+where
+    NoneOf: Parse<'a, I>,
+{
+    Cell::new(NoneOf::new(chars))
+}
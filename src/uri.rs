@@ -0,0 +1,557 @@
+//! URI and HTTP request-target parsing following RFC 3986.
+//!
+//! The parsers validate the generic syntax and split a URI into its
+//! components without decoding them, every component borrows from the input.
+//! Use [`percent_decode`] or [`percent_decode_str`] on the components that
+//! need it.
+
+use crate::cell::Cell;
+use crate::error::{Expected, ParseError};
+use crate::parser::Parse;
+use crate::simd::{self, ByteSet};
+use std::borrow::Cow;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Host<'a> {
+    /// Registered name, which may be empty.
+    Name(&'a str),
+    Ipv4(&'a str),
+    /// IPv6 literal without the brackets.
+    Ipv6(&'a str),
+    /// `v` prefixed future IP literal without the brackets.
+    IpFuture(&'a str),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Authority<'a> {
+    pub userinfo: Option<&'a str>,
+    pub host: Host<'a>,
+    /// Port digits, possibly empty.
+    pub port: Option<&'a str>,
+}
+
+impl<'a> Authority<'a> {
+    /// Port as a number, `None` if it is missing, empty or out of range.
+    pub fn port_number(&self) -> Option<u16> {
+        self.port.and_then(|port| port.parse().ok())
+    }
+}
+
+/// Components of a URI or relative reference.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Uri<'a> {
+    pub scheme: Option<&'a str>,
+    pub authority: Option<Authority<'a>>,
+    pub path: &'a str,
+    pub query: Option<&'a str>,
+    pub fragment: Option<&'a str>,
+}
+
+impl<'a> Uri<'a> {
+    pub fn segments(&self) -> Segments<'a> {
+        segments(self.path)
+    }
+}
+
+/// Target of an HTTP request, RFC 9112 section 3.2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RequestTarget<'a> {
+    /// Absolute path with an optional query, as used by most requests.
+    Origin {
+        path: &'a str,
+        query: Option<&'a str>,
+    },
+    /// Absolute URI without a fragment, as sent to proxies.
+    Absolute(Uri<'a>),
+    /// `host:port`, used by `CONNECT`.
+    Authority(Authority<'a>),
+    /// `*`, used by server wide `OPTIONS`.
+    Asterisk,
+}
+
+/// Iterator over the `/` separated segments of a path, see [`segments`].
+#[derive(Clone, Debug)]
+pub struct Segments<'a> {
+    rest: Option<&'a str>,
+}
+
+impl<'a> Iterator for Segments<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.rest?;
+
+        match rest.find('/') {
+            Some(idx) => {
+                self.rest = Some(&rest[idx + 1..]);
+                Some(&rest[..idx])
+            }
+            None => {
+                self.rest = None;
+                Some(rest)
+            }
+        }
+    }
+}
+
+/// Segments of `path`, ignoring the leading `/` of an absolute path. An
+/// empty path has no segments.
+pub fn segments(path: &str) -> Segments<'_> {
+    let path = path.strip_prefix('/').unwrap_or(path);
+    let rest = if path.is_empty() { None } else { Some(path) };
+
+    Segments { rest }
+}
+
+/// Character classes of the URI grammar.
+#[derive(Clone, Debug)]
+struct Grammar {
+    scheme: ByteSet,
+    userinfo: ByteSet,
+    reg_name: ByteSet,
+    path: ByteSet,
+    query: ByteSet,
+    future: ByteSet,
+}
+
+const UNRESERVED: &[u8] = b"-._~";
+const SUB_DELIMS: &[u8] = b"!$&'()*+,;=";
+
+impl Grammar {
+    fn new() -> Self {
+        let set = |extra: &[&[u8]]| {
+            let mut bytes = Vec::new();
+            bytes.extend(b'a'..=b'z');
+            bytes.extend(b'A'..=b'Z');
+            bytes.extend(b'0'..=b'9');
+            for extra in extra {
+                bytes.extend_from_slice(extra);
+            }
+            ByteSet::new(&bytes)
+        };
+
+        Self {
+            scheme: set(&[b"+-."]),
+            userinfo: set(&[UNRESERVED, SUB_DELIMS, b"%:"]),
+            reg_name: set(&[UNRESERVED, SUB_DELIMS, b"%"]),
+            path: set(&[UNRESERVED, SUB_DELIMS, b"%:@/"]),
+            query: set(&[UNRESERVED, SUB_DELIMS, b"%:@/?"]),
+            future: set(&[UNRESERVED, SUB_DELIMS, b":"]),
+        }
+    }
+
+    /// Longest run of bytes in `set`, with every `%` starting a valid
+    /// percent-encoded octet.
+    fn run<'a>(&self, input: &'a [u8], set: &ByteSet) -> Result<(&'a [u8], &'a str), ParseError> {
+        let len = simd::find_not(input, set).unwrap_or(input.len());
+        let (run, rest) = input.split_at(len);
+        check_escapes(run)?;
+
+        // Every set is ASCII only.
+        Ok((rest, std::str::from_utf8(run).unwrap()))
+    }
+
+    fn scheme<'a>(&self, input: &'a [u8]) -> Result<(&'a [u8], &'a str), ParseError> {
+        let expected = || Expected::Token("scheme".into());
+
+        match input.first() {
+            Some(b) if b.is_ascii_alphabetic() => {}
+            _ => return Err(ParseError::new(input, expected())),
+        }
+
+        let (rest, scheme) = self.run(input, &self.scheme)?;
+        match rest.first() {
+            Some(b':') => Ok((&rest[1..], scheme)),
+            _ => Err(ParseError::new(rest, Expected::Byte(b':'))),
+        }
+    }
+
+    fn authority<'a>(&self, input: &'a [u8]) -> Result<(&'a [u8], Authority<'a>), ParseError> {
+        let (rest, userinfo) = match self.run(input, &self.userinfo)? {
+            (rest, userinfo) if rest.first() == Some(&b'@') => (&rest[1..], Some(userinfo)),
+            _ => (input, None),
+        };
+
+        let (rest, host) = self.host(rest)?;
+
+        let (rest, port) = match rest.first() {
+            Some(b':') => {
+                let rest = &rest[1..];
+                let len = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+                // Digits are ASCII.
+                let port = std::str::from_utf8(&rest[..len]).unwrap();
+                (&rest[len..], Some(port))
+            }
+            _ => (rest, None),
+        };
+
+        let authority = Authority {
+            userinfo,
+            host,
+            port,
+        };
+
+        Ok((rest, authority))
+    }
+
+    fn host<'a>(&self, input: &'a [u8]) -> Result<(&'a [u8], Host<'a>), ParseError> {
+        if input.first() != Some(&b'[') {
+            let (rest, name) = self.run(input, &self.reg_name)?;
+            let host = match name.parse::<Ipv4Addr>() {
+                Ok(_) => Host::Ipv4(name),
+                Err(_) => Host::Name(name),
+            };
+
+            return Ok((rest, host));
+        }
+
+        let inner = &input[1..];
+        let len = inner.iter().position(|&b| b == b']');
+        let literal = match len {
+            Some(len) => std::str::from_utf8(&inner[..len]).ok(),
+            None => None,
+        };
+
+        let host = match literal {
+            Some(literal) if literal.parse::<Ipv6Addr>().is_ok() => Host::Ipv6(literal),
+            Some(literal) if is_ip_future(literal, &self.future) => Host::IpFuture(literal),
+            _ => {
+                let expected = Expected::Token("IP literal".into());
+                return Err(ParseError::new(inner, expected));
+            }
+        };
+
+        Ok((&inner[len.unwrap() + 1..], host))
+    }
+
+    /// `hier-part` or `relative-part`, the authority and path.
+    fn hier_part<'a>(
+        &self,
+        input: &'a [u8],
+        relative: bool,
+    ) -> Result<(&'a [u8], Option<Authority<'a>>, &'a str), ParseError> {
+        let (rest, authority) = match input.strip_prefix(b"//") {
+            Some(rest) => {
+                let (rest, authority) = self.authority(rest)?;
+                (rest, Some(authority))
+            }
+            None => (input, None),
+        };
+
+        // After an authority the path is either empty or absolute.
+        if authority.is_some() && rest.first() != Some(&b'/') {
+            return Ok((rest, authority, ""));
+        }
+
+        let (rest, path) = self.run(rest, &self.path)?;
+
+        // The first segment of a relative path cannot contain a colon, it
+        // would be read as a scheme.
+        if relative && authority.is_none() && !path.starts_with('/') {
+            let segment = path.split('/').next().unwrap();
+            if let Some(idx) = segment.find(':') {
+                let expected = Expected::Token("path segment without ':'".into());
+                return Err(ParseError::new(&input[idx..], expected));
+            }
+        }
+
+        Ok((rest, authority, path))
+    }
+
+    fn suffix<'a>(
+        &self,
+        input: &'a [u8],
+        mark: u8,
+    ) -> Result<(&'a [u8], Option<&'a str>), ParseError> {
+        match input.first() {
+            Some(&b) if b == mark => {
+                let (rest, value) = self.run(&input[1..], &self.query)?;
+                Ok((rest, Some(value)))
+            }
+            _ => Ok((input, None)),
+        }
+    }
+
+    fn uri<'a>(&self, input: &'a [u8], relative: bool) -> Result<(&'a [u8], Uri<'a>), ParseError> {
+        let (rest, scheme) = match self.scheme(input) {
+            Ok((rest, scheme)) => (rest, Some(scheme)),
+            Err(_) if relative => (input, None),
+            Err(err) => return Err(err),
+        };
+
+        let (rest, authority, path) = self.hier_part(rest, scheme.is_none())?;
+        let (rest, query) = self.suffix(rest, b'?')?;
+        let (rest, fragment) = self.suffix(rest, b'#')?;
+
+        let uri = Uri {
+            scheme,
+            authority,
+            path,
+            query,
+            fragment,
+        };
+
+        Ok((rest, uri))
+    }
+}
+
+fn is_ip_future(literal: &str, set: &ByteSet) -> bool {
+    let bytes = literal.as_bytes();
+    let rest = match bytes.first() {
+        Some(b'v') | Some(b'V') => &bytes[1..],
+        _ => return false,
+    };
+
+    let digits = rest.iter().take_while(|b| b.is_ascii_hexdigit()).count();
+    let rest = &rest[digits..];
+
+    digits > 0
+        && rest.first() == Some(&b'.')
+        && rest.len() > 1
+        && rest[1..].iter().all(|&b| set.contains(b))
+}
+
+fn check_escapes(input: &[u8]) -> Result<(), ParseError> {
+    let mut idx = 0;
+
+    while let Some(pos) = input[idx..].iter().position(|&b| b == b'%') {
+        idx += pos;
+        match input.get(idx + 1..idx + 3) {
+            Some([hi, lo]) if hi.is_ascii_hexdigit() && lo.is_ascii_hexdigit() => idx += 3,
+            _ => {
+                let expected = Expected::Token("percent-encoded octet".into());
+                return Err(ParseError::new(&input[idx..], expected));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Decodes percent-encoded octets, and `+` to a space when `plus` is set.
+/// A malformed escape is an error, or kept as it is when `lossy` is set.
+/// Borrows `input` when there is nothing to decode.
+pub(crate) fn decode(input: &[u8], plus: bool, lossy: bool) -> Result<Cow<'_, [u8]>, ParseError> {
+    let start = match input.iter().position(|&b| b == b'%' || (plus && b == b'+')) {
+        Some(start) => start,
+        None => return Ok(Cow::Borrowed(input)),
+    };
+
+    let mut output = input[..start].to_vec();
+    let mut idx = start;
+
+    while idx < input.len() {
+        match input[idx] {
+            b'%' => {
+                // `from_str_radix` alone would accept a sign.
+                let octet = input
+                    .get(idx + 1..idx + 3)
+                    .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+                match octet {
+                    Some(octet) => {
+                        output.push(octet);
+                        idx += 3;
+                    }
+                    None if lossy => {
+                        output.push(b'%');
+                        idx += 1;
+                    }
+                    None => {
+                        let expected = Expected::Token("percent-encoded octet".into());
+                        return Err(ParseError::new(&input[idx..], expected));
+                    }
+                }
+            }
+            b'+' if plus => {
+                output.push(b' ');
+                idx += 1;
+            }
+            b => {
+                output.push(b);
+                idx += 1;
+            }
+        }
+    }
+
+    Ok(Cow::Owned(output))
+}
+
+/// Like [`decode`] but requires the result to be UTF-8.
+pub(crate) fn decode_str(input: &str, plus: bool) -> Result<Cow<'_, str>, ParseError> {
+    match decode(input.as_bytes(), plus, false)? {
+        Cow::Borrowed(_) => Ok(Cow::Borrowed(input)),
+        Cow::Owned(bytes) => String::from_utf8(bytes).map(Cow::Owned).map_err(|err| {
+            // Find the escape that produced the first invalid byte.
+            let mut decoded = 0;
+            let mut idx = 0;
+            while decoded < err.utf8_error().valid_up_to() {
+                idx += if input.as_bytes()[idx] == b'%' { 3 } else { 1 };
+                decoded += 1;
+            }
+
+            let expected = Expected::Token("percent-encoded UTF-8".into());
+            ParseError::new(&input.as_bytes()[idx..], expected)
+        }),
+    }
+}
+
+/// Decodes percent-encoded octets, borrowing `input` when there are none.
+pub fn percent_decode(input: &[u8]) -> Result<Cow<'_, [u8]>, ParseError> {
+    decode(input, false, false)
+}
+
+/// Decodes percent-encoded octets that must form UTF-8.
+pub fn percent_decode_str(input: &str) -> Result<Cow<'_, str>, ParseError> {
+    decode_str(input, false)
+}
+
+/// Parses a URI, or a URI reference if configured to.
+#[derive(Clone, Debug)]
+pub struct UriParser {
+    grammar: Grammar,
+    relative: bool,
+}
+
+impl UriParser {
+    pub fn new() -> Self {
+        Self {
+            grammar: Grammar::new(),
+            relative: false,
+        }
+    }
+
+    /// Accept relative references, which have no scheme.
+    pub fn relative(mut self, relative: bool) -> Self {
+        self.relative = relative;
+        self
+    }
+}
+
+impl Default for UriParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Parse<'a, &'a [u8]> for UriParser {
+    type Output = Uri<'a>;
+
+    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), ParseError> {
+        self.grammar.uri(input, self.relative)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AuthorityParser {
+    grammar: Grammar,
+}
+
+impl AuthorityParser {
+    pub fn new() -> Self {
+        Self {
+            grammar: Grammar::new(),
+        }
+    }
+}
+
+impl Default for AuthorityParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Parse<'a, &'a [u8]> for AuthorityParser {
+    type Output = Authority<'a>;
+
+    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), ParseError> {
+        self.grammar.authority(input)
+    }
+}
+
+/// Parses a request target in any of its four forms.
+///
+/// A target that is exactly `host:port` is read as authority-form, anything
+/// else with a scheme as absolute-form.
+#[derive(Clone, Debug)]
+pub struct RequestTargetParser {
+    grammar: Grammar,
+}
+
+impl RequestTargetParser {
+    pub fn new() -> Self {
+        Self {
+            grammar: Grammar::new(),
+        }
+    }
+}
+
+impl Default for RequestTargetParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Parse<'a, &'a [u8]> for RequestTargetParser {
+    type Output = RequestTarget<'a>;
+
+    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), ParseError> {
+        let grammar = &self.grammar;
+
+        match input.first() {
+            Some(b'*') => return Ok((&input[1..], RequestTarget::Asterisk)),
+            Some(b'/') => {
+                let (rest, path) = grammar.run(input, &grammar.path)?;
+                let (rest, query) = grammar.suffix(rest, b'?')?;
+                return Ok((rest, RequestTarget::Origin { path, query }));
+            }
+            _ => {}
+        }
+
+        // `host:port` is also a scheme and a path, it is authority-form when
+        // nothing of the URI grammar follows the port.
+        if let Ok((rest, authority)) = grammar.authority(input) {
+            let ends = !rest.first().is_some_and(|&b| grammar.query.contains(b));
+            if authority.userinfo.is_none() && authority.port.is_some() && ends {
+                return Ok((rest, RequestTarget::Authority(authority)));
+            }
+        }
+
+        let (rest, scheme) = grammar.scheme(input)?;
+        let (rest, authority, path) = grammar.hier_part(rest, false)?;
+        let (rest, query) = grammar.suffix(rest, b'?')?;
+
+        let uri = Uri {
+            scheme: Some(scheme),
+            authority,
+            path,
+            query,
+            fragment: None,
+        };
+
+        Ok((rest, RequestTarget::Absolute(uri)))
+    }
+}
+
+#[inline]
+pub fn uri<'a>() -> Cell<'a, UriParser> {
+    Cell::new(UriParser::new())
+}
+
+#[inline]
+pub fn uri_reference<'a>() -> Cell<'a, UriParser> {
+    Cell::new(UriParser::new().relative(true))
+}
+
+#[inline]
+pub fn authority<'a>() -> Cell<'a, AuthorityParser> {
+    Cell::new(AuthorityParser::new())
+}
+
+#[inline]
+pub fn request_target<'a>() -> Cell<'a, RequestTargetParser> {
+    Cell::new(RequestTargetParser::new())
+}
@@ -0,0 +1,103 @@
+use parsing::prelude::*;
+
+#[derive(Debug, PartialEq)]
+enum Header<'a> {
+    ContentLength(usize),
+    Other(&'a [u8]),
+}
+
+fn headers<'a>(commit: bool) -> Cell<'a, impl Parse<'a, Output = Vec<Header<'a>>>> {
+    let digits = many1(any_digit()).skip(slice(b"\r\n")).boxed();
+    let digits = if commit { digits.cut().boxed() } else { digits };
+
+    let content_length = slice(b"Content-Length: ")
+        .then(digits)
+        .map(|(_, ds)| Header::ContentLength(ds.iter().collect::<String>().parse().unwrap()));
+    let other = none_of("\r")
+        .then(take_until(slice(b"\r\n")))
+        .recognize()
+        .skip(slice(b"\r\n"))
+        .map(Header::Other);
+
+    many0(content_length.or(other)).skip(slice(b"\r\n"))
+}
+
+#[test]
+fn cut_valid_headers_test() {
+    let bytes = "Host: example.com\r\nContent-Length: 12\r\n\r\n".to_stream();
+
+    let (rest, headers) = headers(true).parse(bytes).unwrap();
+
+    assert_eq!(
+        headers,
+        vec![
+            Header::Other(b"Host: example.com"),
+            Header::ContentLength(12)
+        ]
+    );
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn cut_reports_malformed_header_test() {
+    let bytes = "Host: example.com\r\nContent-Length: 1x\r\n\r\n".to_stream();
+
+    let (_, headers) = headers(false).parse(bytes).unwrap();
+    assert_eq!(headers[1], Header::Other(b"Content-Length: 1x"));
+
+    let err = headers_err(bytes);
+    assert!(err.is_fatal());
+    assert!(!err.is_recoverable());
+    assert_eq!(err.offset(bytes), 36);
+    assert_eq!(err.to_string(), "expected \"\\r\\n\", found 'x'");
+    assert_eq!(err.found(), Some(&Found::Byte(b'x')));
+}
+
+fn headers_err(bytes: &[u8]) -> ParseError {
+    headers(true).parse(bytes).unwrap_err()
+}
+
+#[test]
+fn cut_or_test() {
+    let bytes = "ab".to_stream();
+    let parser = chr('a').then(chr('c').cut()).or(chr('a').then(chr('b')));
+
+    let err = parser.parse(bytes).unwrap_err();
+
+    assert!(err.is_fatal());
+    assert_eq!(err.offset(bytes), 1);
+    assert_eq!(err.expected(), &[Expected::Char('c')]);
+}
+
+#[test]
+fn cut_many1_test() {
+    let bytes = "a1a2ab".to_stream();
+    let parser = many1(chr('a').then(cut(any_digit())));
+
+    let err = parser.parse(bytes).unwrap_err();
+
+    assert!(err.is_fatal());
+    assert_eq!(err.offset(bytes), 5);
+}
+
+#[test]
+fn cut_take_until_test() {
+    let bytes = "xx--y--z".to_stream();
+    let parser = take_until(slice(b"--").then(cut(chr('z'))));
+
+    let err = parser.parse(bytes).unwrap_err();
+
+    assert!(err.is_fatal());
+    assert_eq!(err.offset(bytes), 4);
+    assert_eq!(err.to_string(), "expected 'z', found 'y'");
+}
+
+#[test]
+fn fatal_merge_test() {
+    let bytes = "abc".to_stream();
+    let fatal = ParseError::new(bytes, Expected::Char('x')).into_fatal();
+    let further = ParseError::new(&bytes[2..], Expected::Char('y'));
+
+    assert!(fatal.clone().merge(further.clone()).is_fatal());
+    assert!(further.merge(fatal).is_fatal());
+}
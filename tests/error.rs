@@ -0,0 +1,48 @@
+use parsing::prelude::*;
+
+#[test]
+fn or_merges_expected_test() {
+    let bytes = "GOT / HTTP/1.1\r\n".to_stream();
+    let method = slice(b"GET").or(slice(b"HEAD")).or(slice(b"POST"));
+
+    let err = method.parse(bytes).unwrap_err();
+
+    assert_eq!(err.offset(bytes), 0);
+    assert_eq!(
+        err.expected(),
+        &[
+            Expected::Slice(b"GET".to_vec().into_boxed_slice()),
+            Expected::Slice(b"HEAD".to_vec().into_boxed_slice()),
+            Expected::Slice(b"POST".to_vec().into_boxed_slice()),
+        ]
+    );
+    assert_eq!(err.found(), Some(&Found::Byte(b'G')));
+    assert_eq!(
+        err.to_string(),
+        "expected one of \"GET\", \"HEAD\", \"POST\", found 'G'"
+    );
+}
+
+#[test]
+fn sequence_offset_test() {
+    let bytes = "Host example.com\r\n".to_stream();
+    let header = take_until(chr(' ')).skip(chr(':'));
+
+    let err = header.parse(bytes).unwrap_err();
+
+    assert_eq!(err.offset(bytes), 4);
+    assert_eq!(err.expected(), &[Expected::Char(':')]);
+    assert_eq!(err.to_string(), "expected ':', found ' '");
+}
+
+#[test]
+fn take_until_end_of_input_test() {
+    let bytes = "Host: example.com".to_stream();
+    let line = take_until(slice(b"\r\n"));
+
+    let err = line.parse(bytes).unwrap_err();
+
+    assert_eq!(err.offset(bytes), bytes.len());
+    assert_eq!(err.found(), None);
+    assert_eq!(err.to_string(), "expected \"\\r\\n\", found end of input");
+}
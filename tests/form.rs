@@ -0,0 +1,109 @@
+use parsing::form::{self, Duplicates, FormParser};
+use parsing::prelude::*;
+use parsing::uri::{self, RequestTarget};
+use std::borrow::Cow;
+
+fn pairs(parser: &FormParser, input: &[u8]) -> Vec<(String, String)> {
+    let (rest, pairs) = parser.parse(input).unwrap();
+    assert!(rest.is_empty());
+
+    pairs
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect()
+}
+
+fn owned(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+    pairs
+        .iter()
+        .map(|&(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn form_test() {
+    let parser = FormParser::new();
+
+    assert_eq!(
+        pairs(&parser, b"a=1&b=two+words&c=%26%3D&d"),
+        owned(&[("a", "1"), ("b", "two words"), ("c", "&="), ("d", "")])
+    );
+    assert_eq!(
+        pairs(&parser, b"&&a=1&&=x&b==&"),
+        owned(&[("a", "1"), ("", "x"), ("b", "=")])
+    );
+    assert_eq!(pairs(&parser, b""), owned(&[]));
+}
+
+#[test]
+fn form_borrow_test() {
+    let (_, mut pairs) = form::form().parse(b"key=value&k%20=v".to_stream()).unwrap();
+    assert_eq!(pairs.len(), 2);
+
+    let (key, value) = pairs.next().unwrap();
+    assert!(matches!(key, Cow::Borrowed("key")));
+    assert!(matches!(value, Cow::Borrowed("value")));
+
+    let (key, _) = pairs.next().unwrap();
+    assert!(matches!(key, Cow::Owned(_)));
+    assert_eq!(key, "k ");
+}
+
+#[test]
+fn decode_test() {
+    assert_eq!(form::decode(b"100%25+sure"), "100% sure");
+    assert_eq!(form::decode(b"%zz%4"), "%zz%4");
+    assert_eq!(form::decode(b"%+1+2"), "% 1 2");
+    assert_eq!(form::decode(b"%C3%A9%C3"), "é\u{fffd}");
+    assert_eq!(form::decode(b"caf\xc3\xa9"), "café");
+    assert_eq!(form::decode(b"\xff"), "\u{fffd}");
+}
+
+#[test]
+fn form_duplicates_test() {
+    let input = b"a=1&b=2&a=3&%61=4";
+
+    let parser = FormParser::new();
+    assert_eq!(pairs(&parser, input).len(), 4);
+
+    let parser = FormParser::new().duplicates(Duplicates::First);
+    assert_eq!(pairs(&parser, input), owned(&[("a", "1"), ("b", "2")]));
+
+    let parser = FormParser::new().duplicates(Duplicates::Last);
+    assert_eq!(pairs(&parser, input), owned(&[("a", "4"), ("b", "2")]));
+
+    let parser = FormParser::new().duplicates(Duplicates::Reject);
+    let err = parser.parse(&input[..]).unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(err.offset(&input[..]), 8);
+}
+
+#[test]
+fn form_max_pairs_test() {
+    let parser = FormParser::new().max_pairs(2);
+
+    assert_eq!(
+        pairs(&parser, b"a=1&&b=2&"),
+        owned(&[("a", "1"), ("b", "2")])
+    );
+
+    let input = b"a=1&b=2&c=3".to_stream();
+    let err = parser.parse(input).unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(err.offset(input), 8);
+}
+
+#[test]
+fn form_query_test() {
+    let input = b"/search?q=rust+parser&page=2#results".to_stream();
+    let (_, target) = uri::request_target().parse(input).unwrap();
+
+    let query = match target {
+        RequestTarget::Origin { query, .. } => query.unwrap(),
+        target => panic!("{:?}", target),
+    };
+
+    assert_eq!(
+        pairs(&FormParser::new(), query.as_bytes()),
+        owned(&[("q", "rust parser"), ("page", "2")])
+    );
+}
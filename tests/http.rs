@@ -51,7 +51,7 @@ fn http_parser<'a>() -> impl Parse<'a, Output = Request<'a>> {
         })
 }
 
-fn parse_request<'a>() -> Cell<impl Parse<'a, Output = (&'a str, &'a str, &'a str)>> {
+fn parse_request<'a>() -> Cell<'a, impl Parse<'a, Output = (&'a str, &'a str, &'a str)>> {
     let method = slice(b"GET")
         .or(slice(b"HEAD"))
         .or(slice(b"POST"))
@@ -75,7 +75,7 @@ fn parse_request<'a>() -> Cell<impl Parse<'a, Output = (&'a str, &'a str, &'a st
         .map(|((a, b), c)| (a, b, c))
 }
 
-fn parse_headers<'a>() -> Cell<impl Parse<'a, Output = Vec<(&'a str, &'a str)>>> {
+fn parse_headers<'a>() -> Cell<'a, impl Parse<'a, Output = Vec<(&'a str, &'a str)>>> {
     let header = take_until(chr(':'))
         .skip(slice(b": "))
         .then(take_until(slice(b"\r\n")))
@@ -118,8 +118,8 @@ fn http_test() {
             );
             assert_eq!(xs, &[]);
         }
-        Err(xs) => {
-            panic!("failed to parse bytes: {:?}", to_str(xs));
+        Err(err) => {
+            panic!("failed to parse bytes: {}", err);
         }
     }
 }
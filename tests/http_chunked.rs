@@ -0,0 +1,229 @@
+use parsing::http::{self, Chunk, ChunkHeader, ChunkedDecoder, Header};
+use parsing::prelude::*;
+
+const BODY: &[u8] = b"7\r\nMozilla\r\n\
+    9;name=value;quoted=\"a \\\" b\"\r\nDeveloper\r\n\
+    7 ; last\r\nNetwork\r\n\
+    0\r\nExpires: Wed, 21 Oct 2015 07:28:00 GMT\r\n\r\nnext";
+
+#[test]
+fn chunk_size_test() {
+    let cases: &[(&[u8], u64, &[u8])] = &[
+        (b"0\r\n", 0, b""),
+        (b"1aF\r\n", 0x1af, b""),
+        (b"ffffffffffffffff\r\n", u64::MAX, b""),
+        (b"5;a\r\n", 5, b";a"),
+        (b"5 ; a = \"b\\\"c\" ;d=e\r\n", 5, b" ; a = \"b\\\"c\" ;d=e"),
+    ];
+
+    for &(input, size, extensions) in cases {
+        let (rest, header) = http::chunk_size().parse(input).unwrap();
+        assert_eq!(header, ChunkHeader { size, extensions });
+        assert!(rest.is_empty());
+    }
+}
+
+#[test]
+fn chunk_size_error_test() {
+    let cases: &[(&[u8], usize)] = &[
+        (b"\r\n", 0),
+        (b"g\r\n", 0),
+        (b"5;\r\n", 2),
+        (b"5;a=\r\n", 4),
+        (b"5;a=\"b\x01\"\r\n", 6),
+        (b"5 \r\n", 1),
+        (b"5\n", 1),
+    ];
+
+    for &(input, offset) in cases {
+        let err = http::chunk_size().parse(input).unwrap_err();
+        assert!(err.is_recoverable(), "{}", input.escape_ascii());
+        assert_eq!(err.offset(input), offset, "{}", input.escape_ascii());
+    }
+
+    let input = b"10000000000000000\r\n";
+    let err = http::chunk_size().parse(&input[..]).unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(err.offset(&input[..]), 16);
+}
+
+#[test]
+fn chunk_size_incomplete_test() {
+    let input = b"5;a=\"b\";c\r\n";
+
+    for len in 0..input.len() {
+        let err = http::chunk_size().parse(&input[..len]).unwrap_err();
+        assert!(err.is_incomplete(), "{} bytes: {}", len, err);
+    }
+
+    let parser = ChunkedDecoder::new().max_line_size(8);
+    let err = parser.clone().decode(&input[..9]).unwrap_err();
+    assert!(err.is_fatal());
+}
+
+#[test]
+fn chunk_size_limit_test() {
+    let parser = http::ChunkSize::new().max_line_size(8);
+
+    let (_, header) = parser.parse(&b"5;abcdef\r\n"[..]).unwrap();
+    assert_eq!(header.extensions, b";abcdef");
+
+    // The whole line is buffered, it is still over the limit.
+    let input = b"5;abcdefg\r\n";
+    let err = parser.parse(&input[..]).unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(err.offset(&input[..]), 8);
+
+    let input = b"5;abcdefghij\x01\r\n";
+    let err = parser.parse(&input[..]).unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(err.offset(&input[..]), 8);
+}
+
+#[test]
+fn chunk_data_test() {
+    let (rest, data) = http::chunk_data(4).parse(b"abcdef").unwrap();
+    assert_eq!((rest, data), (&b"ef"[..], &b"abcd"[..]));
+
+    let (rest, data) = http::chunk_data(4).parse(b"ab").unwrap();
+    assert_eq!((rest, data), (&b""[..], &b"ab"[..]));
+
+    let err = http::chunk_data(4).parse(b"").unwrap_err();
+    assert_eq!(err.needed(), Some(Needed::Size(4)));
+}
+
+#[test]
+fn trailers_test() {
+    let (rest, trailers) = http::trailers().parse(b"\r\n").unwrap();
+    assert!(trailers.is_empty() && rest.is_empty());
+
+    let (rest, trailers) = http::trailers().parse(b"A: b\r\n\r\n").unwrap();
+    assert_eq!(
+        trailers,
+        vec![Header {
+            name: "A",
+            value: b"b"
+        }]
+    );
+    assert!(rest.is_empty());
+}
+
+fn decode_all(decoder: &mut ChunkedDecoder, input: &[u8]) -> (Vec<u8>, usize) {
+    let mut body = Vec::new();
+    let mut rest = input;
+
+    loop {
+        match decoder.decode(rest).unwrap() {
+            (next, Chunk::Data(data)) => {
+                body.extend_from_slice(data);
+                rest = next;
+            }
+            (next, Chunk::End(trailers)) => {
+                assert_eq!(trailers[0].name, "Expires");
+                return (body, input.len() - next.len());
+            }
+        }
+    }
+}
+
+#[test]
+fn decoder_test() {
+    let mut decoder = ChunkedDecoder::new();
+    let (body, len) = decode_all(&mut decoder, BODY);
+
+    assert_eq!(body, b"MozillaDeveloperNetwork");
+    assert_eq!(&BODY[len..], b"next");
+    assert!(decoder.is_done());
+    assert!(decoder.decode(b"next").is_err());
+}
+
+#[test]
+fn decoder_split_test() {
+    // Feed the body split at every position, keeping unconsumed bytes the
+    // way a connection buffer would.
+    for split in 0..BODY.len() {
+        let mut decoder = ChunkedDecoder::new();
+        let mut buffer = BODY[..split].to_vec();
+        let mut body = Vec::new();
+        let mut fed = false;
+
+        loop {
+            let consumed = match decoder.decode(&buffer) {
+                Ok((rest, Chunk::Data(data))) => {
+                    body.extend_from_slice(data);
+                    buffer.len() - rest.len()
+                }
+                Ok((rest, Chunk::End(trailers))) => {
+                    assert_eq!(trailers.len(), 1);
+                    assert!(b"next".starts_with(rest));
+                    break;
+                }
+                Err(err) => {
+                    assert!(err.is_incomplete(), "split {}: {}", split, err);
+                    assert!(!fed, "split {}: {}", split, err);
+                    buffer.extend_from_slice(&BODY[split..]);
+                    fed = true;
+                    0
+                }
+            };
+            buffer.drain(..consumed);
+        }
+
+        assert_eq!(body, b"MozillaDeveloperNetwork", "split {}", split);
+    }
+}
+
+#[test]
+fn decoder_into_test() {
+    for size in 1..30 {
+        let mut decoder = ChunkedDecoder::new();
+        let mut buf = vec![0; size];
+        let mut body = Vec::new();
+        let mut rest = BODY;
+
+        loop {
+            match decoder.decode_into(rest, &mut buf).unwrap() {
+                (next, Chunk::Data(written)) => {
+                    assert!(written > 0 && written <= size);
+                    body.extend_from_slice(&buf[..written]);
+                    rest = next;
+                }
+                (next, Chunk::End(trailers)) => {
+                    assert_eq!(trailers.len(), 1);
+                    assert_eq!(next, b"next");
+                    break;
+                }
+            }
+        }
+
+        assert_eq!(body, b"MozillaDeveloperNetwork", "buffer of {}", size);
+    }
+
+    // Data is returned before an incomplete chunk line.
+    let mut decoder = ChunkedDecoder::new();
+    let mut buf = [0; 64];
+    let (rest, written) = decoder.decode_into(&BODY[..15], &mut buf).unwrap();
+    assert_eq!(written, Chunk::Data(7));
+    assert_eq!(rest, b"\r\n9;n");
+
+    let err = decoder.decode_into(rest, &mut buf).unwrap_err();
+    assert!(err.is_incomplete());
+}
+
+#[test]
+fn decoder_bare_lf_test() {
+    let input = b"3\nabc\n0\n\n";
+
+    let err = ChunkedDecoder::new().decode(input).unwrap_err();
+    assert!(!err.is_incomplete());
+
+    let mut decoder = ChunkedDecoder::new().allow_bare_lf(true);
+    assert_eq!(
+        decoder.decode(input).unwrap(),
+        (&input[5..], Chunk::Data(&b"abc"[..]))
+    );
+    assert_eq!(
+        decoder.decode(&input[5..]).unwrap(),
+        (&b""[..], Chunk::End(Vec::new()))
+    );
+}
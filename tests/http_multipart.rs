@@ -0,0 +1,209 @@
+use parsing::http::multipart::{self, Event, MultipartDecoder, MultipartParser};
+use parsing::http::{self, Header};
+use parsing::prelude::*;
+
+const BODY: &[u8] = b"preamble\r\n\
+    --AaB03x\r\n\
+    Content-Disposition: form-data; name=\"submit-name\"\r\n\r\n\
+    Larry\r\n\
+    --AaB03x  \r\n\
+    Content-Disposition: form-data; name=\"files\"; filename=\"file1.txt\"\r\n\
+    Content-Type: text/plain\r\n\r\n\
+    ... contents of file1.txt ...\r\n--AaB\r\n\
+    --AaB03x\r\n\
+    Content-Disposition: form-data; name=\"empty\"\r\n\r\n\
+    \r\n\
+    --AaB03x--\r\nepilogue";
+
+#[test]
+fn boundary_test() {
+    let cases: &[(&[u8], Option<&[u8]>)] = &[
+        (b"multipart/form-data; boundary=AaB03x", Some(b"AaB03x")),
+        (b"Multipart/Form-Data;boundary=\"a b:c\"", Some(b"a b:c")),
+        (
+            b"multipart/mixed; charset=utf-8; BOUNDARY=x ; y=z",
+            Some(b"x"),
+        ),
+        (b"multipart/form-data", None),
+        (b"multipart/form-data; boundary=", None),
+        (b"text/plain; boundary=AaB03x", None),
+    ];
+
+    for &(content_type, boundary) in cases {
+        assert_eq!(multipart::boundary(content_type), boundary);
+    }
+}
+
+#[test]
+fn multipart_test() {
+    let (rest, parts) = MultipartParser::new(b"AaB03x").parse(BODY).unwrap();
+
+    assert_eq!(parts.len(), 3);
+    assert_eq!(parts[0].name().as_deref(), Some("submit-name"));
+    assert_eq!(parts[0].filename(), None);
+    assert_eq!(parts[0].body, b"Larry");
+
+    assert_eq!(parts[1].name().as_deref(), Some("files"));
+    assert_eq!(parts[1].filename().as_deref(), Some("file1.txt"));
+    assert_eq!(parts[1].content_type(), Some(&b"text/plain"[..]));
+    assert_eq!(parts[1].body, b"... contents of file1.txt ...\r\n--AaB");
+
+    assert_eq!(parts[2].disposition().unwrap().kind, "form-data");
+    assert_eq!(parts[2].body, b"");
+
+    assert_eq!(rest, b"\r\nepilogue");
+}
+
+#[test]
+fn multipart_disposition_test() {
+    let input = b"--b\r\n\
+        Content-Disposition: form-data; filename=\"a \\\"quoted\\\" name.txt\"; NAME=upload\r\n\r\n\
+        x\r\n--b--";
+
+    let (_, parts) = MultipartParser::new(b"b").parse(&input[..]).unwrap();
+
+    assert_eq!(parts[0].name().as_deref(), Some("upload"));
+    assert_eq!(
+        parts[0].filename().as_deref(),
+        Some("a \"quoted\" name.txt")
+    );
+}
+
+#[test]
+fn multipart_http_test() {
+    let input = b"POST /upload HTTP/1.1\r\n\
+        Content-Type: multipart/form-data; boundary=\"xyz\"\r\n\r\n\
+        --xyz\r\nContent-Disposition: form-data; name=\"a\"\r\n\r\n1\r\n--xyz--";
+
+    let (body, request) = http::request().parse(&input[..]).unwrap();
+    let boundary = multipart::boundary(request.header("content-type").unwrap()).unwrap();
+    let (_, parts) = MultipartParser::new(boundary).parse(body).unwrap();
+
+    assert_eq!(
+        parts[0].headers,
+        vec![Header {
+            name: "Content-Disposition",
+            value: b"form-data; name=\"a\""
+        }]
+    );
+    assert_eq!(parts[0].body, b"1");
+}
+
+#[test]
+fn multipart_error_test() {
+    let parser = MultipartParser::new(b"AaB03x");
+
+    let err = parser.parse(&BODY[..BODY.len() - 20]).unwrap_err();
+    assert!(err.is_incomplete());
+
+    let input = b"--AaB03x\r\nBad Header: x\r\n\r\n\r\n--AaB03x--";
+    let err = parser.parse(&input[..]).unwrap_err();
+    assert_eq!(err.offset(&input[..]), 13);
+
+    let input = b"--AaB03x\r\n\r\n\r\n--AaB03xyz\r\n\r\n\r\n--AaB03x--";
+    let err = parser.parse(&input[..]).unwrap_err();
+    assert_eq!(err.offset(&input[..]), 22);
+
+    let parser = MultipartParser::new(b"AaB03x").max_headers(1);
+    let input = b"--AaB03x\r\nA: 1\r\nB: 2\r\n\r\n\r\n--AaB03x--";
+    assert!(parser.parse(&input[..]).unwrap_err().is_fatal());
+}
+
+#[test]
+fn multipart_preamble_test() {
+    // Lines that only start with the boundary belong to the preamble.
+    let parser = MultipartParser::new(b"XyZ");
+    let cases: &[&[u8]] = &[
+        b"--XyZX\r\n--XyZ\r\n\r\nbody\r\n--XyZ--",
+        b"junk\r\n--XyZX\r\n--XyZ\r\n\r\nbody\r\n--XyZ--",
+        b"--XyZ-x\r\n--XyZ \t\r\n\r\nbody\r\n--XyZ--",
+    ];
+
+    for &input in cases {
+        let (rest, parts) = parser.parse(input).unwrap();
+        assert_eq!(parts.len(), 1, "{}", input.escape_ascii());
+        assert_eq!(parts[0].body, b"body");
+        assert!(rest.is_empty());
+    }
+
+    let err = parser.parse(&b"--XyZX\r\n--XyZ"[..]).unwrap_err();
+    assert!(err.is_incomplete());
+}
+
+type OwnedHeaders = Vec<(String, Vec<u8>)>;
+
+fn owned(headers: &[Header]) -> OwnedHeaders {
+    headers
+        .iter()
+        .map(|header| (header.name.to_string(), header.value.to_vec()))
+        .collect()
+}
+
+/// Decodes `BODY` fed in pieces of at most `size` bytes, keeping unconsumed
+/// bytes the way a connection buffer would.
+fn stream(size: usize) -> Vec<(OwnedHeaders, Vec<u8>)> {
+    let mut decoder = MultipartDecoder::new(b"AaB03x");
+    let mut parts: Vec<(OwnedHeaders, Vec<u8>)> = Vec::new();
+    let mut buffer = Vec::new();
+    let mut fed = 0;
+
+    loop {
+        let consumed = match decoder.decode(&buffer) {
+            Ok((rest, Event::Headers(headers))) => {
+                parts.push((owned(&headers), Vec::new()));
+                buffer.len() - rest.len()
+            }
+            Ok((rest, Event::Data(data))) => {
+                assert!(!data.is_empty());
+                parts.last_mut().unwrap().1.extend_from_slice(data);
+                buffer.len() - rest.len()
+            }
+            Ok((rest, Event::End)) => {
+                assert!(b"\r\nepilogue".starts_with(rest));
+                assert!(decoder.is_done());
+                return parts;
+            }
+            Err(err) => {
+                assert!(err.is_incomplete(), "{}", err);
+                assert!(fed < BODY.len(), "{}", err);
+                let end = (fed + size).min(BODY.len());
+                buffer.extend_from_slice(&BODY[fed..end]);
+                fed = end;
+                0
+            }
+        };
+        buffer.drain(..consumed);
+    }
+}
+
+#[test]
+fn multipart_stream_test() {
+    let (_, expected) = MultipartParser::new(b"AaB03x").parse(BODY).unwrap();
+
+    for size in 1..BODY.len() {
+        let parts = stream(size);
+
+        assert_eq!(parts.len(), expected.len(), "pieces of {}", size);
+        for (part, expected) in parts.iter().zip(&expected) {
+            assert_eq!(part.0, owned(&expected.headers), "pieces of {}", size);
+            assert_eq!(part.1, expected.body, "pieces of {}", size);
+        }
+    }
+}
+
+#[test]
+fn multipart_stream_data_test() {
+    // Body data is released before the delimiter shows up, except for a
+    // tail that may be its start.
+    let mut decoder = MultipartDecoder::new(b"AaB03x");
+    let input = b"--AaB03x\r\n\r\n0123456789\r\n--AaB";
+
+    let (rest, event) = decoder.decode(&input[..]).unwrap();
+    assert_eq!(event, Event::Headers(Vec::new()));
+
+    let (rest, event) = decoder.decode(rest).unwrap();
+    assert_eq!(event, Event::Data(&b"0123456789"[..]));
+    assert_eq!(rest, b"\r\n--AaB");
+
+    assert!(decoder.decode(rest).unwrap_err().is_incomplete());
+}
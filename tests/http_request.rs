@@ -0,0 +1,139 @@
+use parsing::http::{self, Header, Method, RequestParser, Version};
+use parsing::prelude::*;
+
+const REQUEST: &[u8] = b"GET /index.html HTTP/1.1\r\n\
+    User-Agent: Mozilla/4.0 (compatible; MSIE5.01; Windows NT)\r\n\
+    Accept-Language: en-us\r\n\
+    Accept-Encoding:gzip, deflate  \r\n\
+    Connection: Keep-Alive\r\n\r\nbody";
+
+#[test]
+fn request_test() {
+    let (rest, request) = http::request().parse(REQUEST).unwrap();
+
+    assert_eq!(request.method, Method::Get);
+    assert_eq!(request.target, "/index.html");
+    assert_eq!(request.version, Version::Http11);
+    assert_eq!(
+        request.headers,
+        vec![
+            Header {
+                name: "User-Agent",
+                value: b"Mozilla/4.0 (compatible; MSIE5.01; Windows NT)"
+            },
+            Header {
+                name: "Accept-Language",
+                value: b"en-us"
+            },
+            Header {
+                name: "Accept-Encoding",
+                value: b"gzip, deflate"
+            },
+            Header {
+                name: "Connection",
+                value: b"Keep-Alive"
+            },
+        ]
+    );
+    assert_eq!(request.header("connection"), Some(&b"Keep-Alive"[..]));
+    assert_eq!(request.header("Host"), None);
+    assert_eq!(rest, b"body");
+}
+
+#[test]
+fn request_method_test() {
+    let (_, request) = http::request()
+        .parse(b"PROPFIND /dav HTTP/1.0\r\n\r\n".to_stream())
+        .unwrap();
+
+    assert_eq!(request.method, Method::Other("PROPFIND"));
+    assert_eq!(request.method.as_str(), "PROPFIND");
+    assert_eq!(request.version, Version::Http10);
+    assert!(request.headers.is_empty());
+}
+
+#[test]
+fn request_incomplete_test() {
+    let end = REQUEST.len() - 4;
+
+    for len in 0..end {
+        let err = http::request().parse(&REQUEST[..len]).unwrap_err();
+        assert!(err.is_incomplete(), "{} bytes: {}", len, err);
+    }
+
+    assert!(http::request().parse(&REQUEST[..end]).is_ok());
+}
+
+#[test]
+fn request_grammar_test() {
+    let cases: &[(&[u8], usize)] = &[
+        (b"GET  / HTTP/1.1\r\n\r\n", 4),
+        (b"GET / HTTP/2.0\r\n\r\n", 6),
+        (b"G(T / HTTP/1.1\r\n\r\n", 1),
+        (b"GET /a\x7fb HTTP/1.1\r\n\r\n", 6),
+        (b"GET / HTTP/1.1\r\nHost : x\r\n\r\n", 20),
+        (b"GET / HTTP/1.1\r\nHost: a\x00b\r\n\r\n", 23),
+        (b"GET / HTTP/1.1\r\nHost: a\rb\r\n\r\n", 23),
+    ];
+
+    for &(input, offset) in cases {
+        let err = http::request().parse(input).unwrap_err();
+        assert!(!err.is_incomplete(), "{:?}", input.escape_ascii().to_string());
+        assert_eq!(err.offset(input), offset, "{}", input.escape_ascii());
+    }
+}
+
+#[test]
+fn request_obs_text_test() {
+    let input = b"GET / HTTP/1.1\r\nX-Name: caf\xc3\xa9 \xff\r\n\r\n";
+
+    let (_, request) = http::request().parse(&input[..]).unwrap();
+
+    assert_eq!(request.headers[0].value, b"caf\xc3\xa9 \xff");
+    assert_eq!(request.headers[0].value_str(), None);
+}
+
+#[test]
+fn request_bare_lf_test() {
+    let input = b"GET / HTTP/1.1\nHost: example.com\n\n".to_stream();
+
+    let err = http::request().parse(input).unwrap_err();
+    assert_eq!(err.offset(input), 14);
+
+    let parser = RequestParser::new().allow_bare_lf(true);
+    let (rest, request) = parser.parse(input).unwrap();
+    assert_eq!(request.header("host"), Some(&b"example.com"[..]));
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn request_obs_fold_test() {
+    let input = b"GET / HTTP/1.1\r\nX-Long: a\r\n  b\r\nHost: x\r\n\r\n".to_stream();
+
+    let err = http::request().parse(input).unwrap_err();
+    assert_eq!(err.offset(input), 27);
+
+    let parser = RequestParser::new().allow_obs_fold(true);
+    let (_, request) = parser.parse(input).unwrap();
+    assert_eq!(request.header("x-long"), Some(&b"a\r\n  b"[..]));
+    assert_eq!(request.header("host"), Some(&b"x"[..]));
+}
+
+#[test]
+fn request_limits_test() {
+    let parser = RequestParser::new().max_headers(3);
+    let err = parser.parse(REQUEST).unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(err.to_string(), "expected at most 3 header fields, found 'C'");
+
+    let parser = RequestParser::new().max_head_size(64);
+    let err = parser.parse(REQUEST).unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(err.offset(REQUEST), 64);
+
+    // A head that never ends fails once it is over the limit.
+    let err = parser.parse(&REQUEST[..70]).unwrap_err();
+    assert!(err.is_fatal());
+    let err = parser.parse(&REQUEST[..60]).unwrap_err();
+    assert!(err.is_incomplete());
+}
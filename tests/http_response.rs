@@ -0,0 +1,160 @@
+use parsing::http::{self, Header, ResponseParser, Version};
+use parsing::prelude::*;
+
+const RESPONSE: &[u8] = b"HTTP/1.1 200 OK\r\n\
+    Content-Type: text/html; charset=utf-8\r\n\
+    Content-Length: 4\r\n\r\nbody";
+
+#[test]
+fn response_test() {
+    let (rest, response) = http::response().parse(RESPONSE).unwrap();
+
+    assert_eq!(response.version, Version::Http11);
+    assert_eq!(response.status, 200);
+    assert_eq!(response.reason_str(), Some("OK"));
+    assert_eq!(
+        response.headers,
+        vec![
+            Header {
+                name: "Content-Type",
+                value: b"text/html; charset=utf-8"
+            },
+            Header {
+                name: "Content-Length",
+                value: b"4"
+            },
+        ]
+    );
+    assert_eq!(response.header("content-length"), Some(&b"4"[..]));
+    assert!(!response.is_interim());
+    assert_eq!(rest, b"body");
+}
+
+#[test]
+fn response_reason_test() {
+    let cases: &[(&[u8], &[u8])] = &[
+        (b"HTTP/1.0 404 Not Found\r\n\r\n", b"Not Found"),
+        (b"HTTP/1.1 204 \r\n\r\n", b""),
+        (b"HTTP/1.1 204\r\n\r\n", b""),
+        (b"HTTP/1.1 500 Erreur \xe9\tinterne\r\n\r\n", b"Erreur \xe9\tinterne"),
+    ];
+
+    for &(input, reason) in cases {
+        let (rest, response) = http::response().parse(input).unwrap();
+        assert_eq!(response.reason, reason);
+        assert!(rest.is_empty());
+    }
+}
+
+#[test]
+fn response_incomplete_test() {
+    let end = RESPONSE.len() - 4;
+
+    for len in 0..end {
+        let err = http::response().parse(&RESPONSE[..len]).unwrap_err();
+        assert!(err.is_incomplete(), "{} bytes: {}", len, err);
+    }
+}
+
+#[test]
+fn response_status_test() {
+    let cases: &[(&[u8], usize)] = &[
+        (b"HTTP/1.1 20 OK\r\n\r\n", 11),
+        (b"HTTP/1.1 2000 OK\r\n\r\n", 12),
+        (b"HTTP/1.1 abc OK\r\n\r\n", 9),
+        (b"HTTP/1.1 200 O\x00K\r\n\r\n", 14),
+        (b"HTTP/1.1  200 OK\r\n\r\n", 9),
+    ];
+
+    for &(input, offset) in cases {
+        let err = http::response().parse(input).unwrap_err();
+        assert!(!err.is_incomplete(), "{}", input.escape_ascii());
+        assert_eq!(err.offset(input), offset, "{}", input.escape_ascii());
+    }
+}
+
+#[test]
+fn response_interim_test() {
+    let input = b"HTTP/1.1 100 Continue\r\n\r\n\
+        HTTP/1.1 103 Early Hints\r\nLink: </style.css>\r\n\r\n\
+        HTTP/1.1 201 Created\r\n\r\n";
+
+    let (rest, response) = http::response().parse(&input[..]).unwrap();
+    assert_eq!(response.status, 100);
+    assert!(response.is_interim());
+
+    let (_, response) = http::response().parse(rest).unwrap();
+    assert_eq!(response.status, 103);
+    assert_eq!(response.header("link"), Some(&b"</style.css>"[..]));
+
+    let parser = ResponseParser::new().skip_interim(true);
+    let (rest, response) = parser.parse(&input[..]).unwrap();
+    assert_eq!(response.status, 201);
+    assert!(rest.is_empty());
+
+    let err = parser.parse(&input[..input.len() - 2]).unwrap_err();
+    assert!(err.is_incomplete());
+}
+
+#[test]
+fn response_switching_protocols_test() {
+    let input = b"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\r\n\x81\x05";
+
+    let parser = ResponseParser::new().skip_interim(true);
+    let (rest, response) = parser.parse(&input[..]).unwrap();
+
+    assert_eq!(response.status, 101);
+    assert!(!response.is_interim());
+    assert_eq!(rest, b"\x81\x05");
+}
+
+#[test]
+fn response_max_headers_test() {
+    let head = b"HTTP/1.1 200 OK\r\nA: 1\r\nB: 2\r\n\r\n";
+    let over = b"HTTP/1.1 200 OK\r\nA: 1\r\nB: 2\r\nC: 3\r\n\r\n";
+
+    for &trailer in &[&b""[..], b"body\n", b"HTTP/1.1 200 OK\r\n\r\n"] {
+        let input = [&head[..], trailer].concat();
+        let (rest, response) = ResponseParser::new().max_headers(2).parse(&input[..]).unwrap();
+        assert_eq!(response.headers.len(), 2);
+        assert_eq!(rest, trailer);
+
+        let input = [&over[..], trailer].concat();
+        let err = ResponseParser::new().max_headers(2).parse(&input[..]).unwrap_err();
+        assert!(err.is_fatal());
+        assert_eq!(err.offset(&input[..]), 29);
+    }
+
+    let input = b"HTTP/1.1 204 No Content\r\n\r\nHTTP/1.1 200 OK\r\nA: 1\r\n\r\n";
+    let parser = ResponseParser::new().max_headers(0);
+    let (rest, response) = parser.parse(&input[..]).unwrap();
+    assert!(response.headers.is_empty());
+    assert!(parser.parse(rest).unwrap_err().is_fatal());
+}
+
+#[test]
+fn response_options_test() {
+    let input = b"HTTP/1.1 200 OK\nVary: Accept,\r\n Cookie\r\n\r\n";
+
+    let err = http::response().parse(&input[..]).unwrap_err();
+    assert_eq!(err.offset(&input[..]), 15);
+    assert!(!err.is_incomplete());
+
+    let parser = ResponseParser::new().allow_bare_lf(true).allow_obs_fold(true);
+    let (rest, response) = parser.parse(&input[..]).unwrap();
+    assert_eq!(response.header("vary"), Some(&b"Accept,\r\n Cookie"[..]));
+    assert!(rest.is_empty());
+
+    let input = b"HTTP/1.1 200 OK\r\nA: 1\r\nB: 2\r\nC 3\r\n\r\n";
+    let err = http::response().parse(&input[..]).unwrap_err();
+    assert_eq!(err.offset(&input[..]), 30);
+
+    let err = ResponseParser::new().max_headers(2).parse(&input[..]).unwrap_err();
+    assert!(err.is_fatal());
+    assert_eq!(err.offset(&input[..]), 29);
+
+    let err = ResponseParser::new().max_head_size(16).parse(&input[..]).unwrap_err();
+    assert!(err.is_fatal());
+    let err = ResponseParser::new().max_head_size(16).parse(&input[..20]).unwrap_err();
+    assert!(err.is_fatal());
+}
@@ -0,0 +1,86 @@
+use parsing::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Tok {
+    Ident(&'static str),
+    Num(i64),
+    Plus,
+    LParen,
+    RParen,
+}
+
+impl Token for Tok {}
+
+fn num<'a>() -> Cell<'a, impl Parse<'a, &'a [Tok], Output = i64>, &'a [Tok]> {
+    satisfy(|tok: &Tok| matches!(tok, Tok::Num(_))).map(|tok: &Tok| match *tok {
+        Tok::Num(n) => n,
+        _ => unreachable!(),
+    })
+}
+
+#[test]
+fn token_input_test() {
+    let tokens = [Tok::Num(1), Tok::Plus, Tok::Num(2), Tok::Plus, Tok::Num(3)];
+    let sum = num()
+        .then(many0(just(Tok::Plus).then(num()).map(|(_, n)| n)))
+        .map(|(first, rest)| first + rest.iter().sum::<i64>());
+
+    let (rest, value) = sum.parse(&tokens[..]).unwrap();
+
+    assert_eq!(value, 6);
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn token_error_test() {
+    let tokens = [Tok::LParen, Tok::Ident("x"), Tok::Plus, Tok::RParen];
+    let group = just(Tok::LParen)
+        .then(just(Tok::Ident("x")))
+        .then(just(Tok::RParen));
+
+    let err = group.parse(&tokens[..]).unwrap_err();
+
+    assert_eq!(err.offset(&tokens[..]), 2);
+    assert_eq!(err.expected(), &[Expected::Token("RParen".to_string())]);
+    assert_eq!(err.found(), Some(&Found::Token("Plus".to_string())));
+    assert_eq!(err.to_string(), "expected RParen, found Plus");
+}
+
+#[test]
+fn str_input_test() {
+    let input = "naïve: café\n";
+    let key = take_until(chr(':')).skip(slice(b": "));
+    let value = take_until(chr('\n'));
+
+    let (rest, (key, value)) = key.then(value).parse(input).unwrap();
+
+    assert_eq!(key, "naïve");
+    assert_eq!(value, "café");
+    assert_eq!(rest, "\n");
+}
+
+#[test]
+fn str_char_boundary_test() {
+    // "é" is encoded as C3 A9, the literal stops inside it.
+    let err = slice(b"\xC3").parse("é").unwrap_err();
+    assert_eq!(err.offset("é"), 0);
+    assert!(err.is_recoverable());
+
+    let err = streaming::slice(b"\xC3").parse("é").unwrap_err();
+    assert!(!err.is_incomplete());
+
+    let either = slice(b"\xC3").or(slice("é".as_bytes()));
+    assert_eq!(either.parse("éa").unwrap(), ("a", "é"));
+}
+
+#[test]
+fn borrowed_input_test() {
+    let method = slice(b"GET").or(slice(b"PUT"));
+    assert_eq!(method.parse(b"PUT /").unwrap(), (&b" /"[..], &b"PUT"[..]));
+
+    let buffer = b"GET /".to_vec();
+    assert_eq!(method.parse(&buffer).unwrap().0, b" /");
+
+    let line = String::from("naïve\n");
+    assert_eq!(take_until(chr('\n')).parse(&line).unwrap(), ("\n", "naïve"));
+}
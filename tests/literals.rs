@@ -0,0 +1,104 @@
+use parsing::prelude::*;
+
+const METHODS: &[&[u8]] = &[
+    b"GET", b"HEAD", b"POST", b"PUT", b"DELETE", b"CONNECT", b"OPTIONS", b"TRACE", b"PATCH",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Op {
+    Lt,
+    Le,
+    Shl,
+    ShlAssign,
+}
+
+#[test]
+fn one_of_literals_test() {
+    for &name in METHODS {
+        let bytes = [name, b" /"].concat();
+        let (rest, output) = one_of_literals(METHODS).parse(&bytes[..]).unwrap();
+        assert_eq!((rest, output), (&b" /"[..], name));
+    }
+
+    let method = one_of_literals(METHODS);
+
+    assert!(method.parse(b"PUSH".to_stream()).is_err());
+    assert!(method.parse(b"GE".to_stream()).is_err());
+}
+
+#[test]
+fn longest_match_test() {
+    // Unlike a chain of `or`, the order of the literals does not matter.
+    let op = keywords(&[
+        (&b"<"[..], Op::Lt),
+        (b"<=", Op::Le),
+        (b"<<", Op::Shl),
+        (b"<<=", Op::ShlAssign),
+    ]);
+
+    assert_eq!(
+        op.parse(b"<<= 1".to_stream()).unwrap(),
+        (&b" 1"[..], Op::ShlAssign)
+    );
+    assert_eq!(op.parse(b"<<1".to_stream()).unwrap(), (&b"1"[..], Op::Shl));
+    assert_eq!(op.parse(b"<=".to_stream()).unwrap().1, Op::Le);
+    assert_eq!(op.parse(b"<-".to_stream()).unwrap(), (&b"-"[..], Op::Lt));
+
+    // A literal that only matches partly falls back to a shorter one.
+    let words = one_of_literals(&[b"for", b"format"]);
+    assert_eq!(
+        words.parse(b"forma".to_stream()).unwrap(),
+        (&b"ma"[..], &b"for"[..])
+    );
+}
+
+#[test]
+fn str_input_test() {
+    let keyword = keywords(&[(&b"true"[..], true), (b"false", false)]);
+
+    assert_eq!(keyword.parse("false,").unwrap(), (",", false));
+    assert_eq!(
+        one_of_literals(&[b"ab", b"a"]).parse("abc").unwrap(),
+        ("c", "ab")
+    );
+
+    // "é" is encoded as C3 A9, a literal that stops inside it does not match.
+    let prefix = keywords(&[(&b"\xC3"[..], 1), (b"", 0)]);
+    assert_eq!(prefix.parse("é").unwrap(), ("é", 0));
+    assert!(one_of_literals(&[b"\xC3"]).parse("é").is_err());
+}
+
+#[test]
+fn error_test() {
+    let bytes = b"BREW /".to_stream();
+    let err = one_of_literals(METHODS).parse(bytes).unwrap_err();
+
+    // Same error as the chain of `or` it replaces.
+    let chained = slice(b"GET")
+        .or(slice(b"HEAD"))
+        .or(slice(b"POST"))
+        .or(slice(b"PUT"))
+        .or(slice(b"DELETE"))
+        .or(slice(b"CONNECT"))
+        .or(slice(b"OPTIONS"))
+        .or(slice(b"TRACE"))
+        .or(slice(b"PATCH"));
+
+    assert_eq!(err, chained.parse(bytes).unwrap_err());
+    assert_eq!(err.offset(bytes), 0);
+    assert_eq!(err.expected().len(), METHODS.len());
+}
+
+#[test]
+fn empty_literal_test() {
+    let sign = keywords(&[(&b"-"[..], -1), (b"+", 1), (b"", 1)]);
+
+    assert_eq!(sign.parse(b"-5".to_stream()).unwrap(), (&b"5"[..], -1));
+    assert_eq!(sign.parse(b"5".to_stream()).unwrap(), (&b"5"[..], 1));
+}
+
+#[test]
+#[should_panic]
+fn empty_set_test() {
+    one_of_literals::<&[u8]>(&[]);
+}
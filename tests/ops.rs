@@ -0,0 +1,48 @@
+use parsing::prelude::*;
+
+#[test]
+fn bitor_test() {
+    let parser = slice(b"GET") | slice(b"HEAD") | slice(b"POST");
+
+    assert_eq!(parser.parse(b"HEAD /".to_stream()).unwrap(), (&b" /"[..], &b"HEAD"[..]));
+    assert_eq!(parser.parse(b"POST".to_stream()).unwrap().1, b"POST");
+    assert!(parser.parse(b"PUT".to_stream()).is_err());
+}
+
+#[test]
+fn bitand_add_test() {
+    let bytes = b"ab!".to_stream();
+
+    let (rest, (a, b)) = (byte(b'a') & byte(b'b')).parse(bytes).unwrap();
+    assert_eq!((*a, *b), (b'a', b'b'));
+    assert_eq!(rest, b"!");
+
+    let (rest, ((a, b), c)) = (byte(b'a') + byte(b'b') + byte(b'!')).parse(bytes).unwrap();
+    assert_eq!((*a, *b, *c), (b'a', b'b', b'!'));
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn shift_test() {
+    let bytes = b"(x)".to_stream();
+    let parser = byte(b'(') >> byte(b'x') << byte(b')');
+
+    let (rest, x) = parser.parse(bytes).unwrap();
+    assert_eq!(*x, b'x');
+    assert!(rest.is_empty());
+
+    assert!(parser.parse(b"(x".to_stream()).is_err());
+    assert!(parser.parse(b"x)".to_stream()).is_err());
+}
+
+#[test]
+fn shr_order_test() {
+    let bytes = b"-1".to_stream();
+
+    assert_eq!((byte(b'-') >> byte(b'1')).parse(bytes).unwrap().1, &b'1');
+    assert!((byte(b'1') >> byte(b'-')).parse(bytes).is_err());
+
+    // `skip_left` runs its argument first and keeps its output.
+    assert_eq!(byte(b'1').skip_left(byte(b'-')).parse(bytes).unwrap().1, &b'-');
+    assert_eq!(skip_left(byte(b'1'), byte(b'-')).parse(bytes).unwrap().1, &b'-');
+}
@@ -0,0 +1,118 @@
+use parsing::prelude::*;
+
+/// Single ASCII digit, written without any of the combinators.
+struct Digit;
+
+impl<'a> Parse<'a> for Digit {
+    type Output = u8;
+
+    fn parse(&self, input: &'a [u8]) -> Result<(&'a [u8], Self::Output), ParseError> {
+        match input.split_first() {
+            Some((&b, rest)) if b.is_ascii_digit() => Ok((rest, b - b'0')),
+            _ => Err(ParseError::new(input, Expected::Digit)),
+        }
+    }
+}
+
+fn number<'a>() -> impl Parse<'a, Output = u16> {
+    Digit
+        .many1()
+        .recognize()
+        .map_res(|digits: &[u8]| std::str::from_utf8(digits).unwrap().parse::<u16>())
+}
+
+#[test]
+fn raw_parser_test() {
+    let bytes = b"GET /".to_stream();
+    let method = Slice::new(b"GET")
+        .or(Slice::new(b"PUT"))
+        .skip(Byte::new(b' '))
+        .map(|method: &[u8]| method.len());
+
+    assert_eq!(method.parse(bytes).unwrap(), (&b"/"[..], 3));
+
+    let (rest, digits) = Digit.sep_by1(Byte::new(b',')).parse(b"1,2,3;".to_stream()).unwrap();
+    assert_eq!(digits, vec![1, 2, 3]);
+    assert_eq!(rest, b";");
+
+    let sum = Digit.fold_many0(|| 0u32, |sum, digit| sum + digit as u32);
+    assert_eq!(sum.parse(b"999".to_stream()).unwrap().1, 27);
+}
+
+#[test]
+fn map_res_test() {
+    assert_eq!(number().parse(b"8080/".to_stream()).unwrap(), (&b"/"[..], 8080));
+
+    let bytes = b"99999".to_stream();
+    let err = number().parse(bytes).unwrap_err();
+    assert_eq!(err.offset(bytes), 0);
+    assert!(err.is_recoverable());
+}
+
+#[test]
+fn and_then_test() {
+    let field = TakeUntil::new(Byte::new(b';')).and_then(Digit.many1().skip(Byte::new(b'!')));
+
+    let (rest, digits) = field.parse(b"12!;".to_stream()).unwrap();
+    assert_eq!(digits, vec![1, 2]);
+    assert_eq!(rest, b";");
+
+    // The inner error is reported in terms of the outer input, and is not
+    // incomplete even though the inner parser ran out of input.
+    let bytes = b"12;".to_stream();
+    let err = field.parse(bytes).unwrap_err();
+    assert_eq!(err.offset(bytes), 2);
+    assert!(!err.is_incomplete());
+
+    // The output of `>>` starts after the bracket.
+    let bracketed = (slice(b"[") >> take_until(chr(']'))).and_then(chr('x').skip(chr('y')));
+    let err = bracketed.parse("[abc]").unwrap_err();
+    assert_eq!(err.offset("[abc]"), 1);
+
+    let err = bracketed.parse("[xé]").unwrap_err();
+    assert_eq!(err.offset("[xé]"), 2);
+}
+
+#[test]
+fn flat_map_test() {
+    let prefixed = Digit.flat_map(|len| Digit.count(len as usize));
+
+    let (rest, digits) = prefixed.parse(b"3123".to_stream()).unwrap();
+    assert_eq!(digits, vec![1, 2, 3]);
+    assert!(rest.is_empty());
+
+    assert!(prefixed.parse(b"312".to_stream()).is_err());
+}
+
+#[test]
+fn value_verify_test() {
+    let yes = Slice::new(b"yes").value(true).or(Slice::new(b"no").value(false));
+    assert!(!yes.parse(b"no".to_stream()).unwrap().1);
+
+    let even = Digit.verify(|digit| digit % 2 == 0);
+    assert_eq!(even.parse(b"4".to_stream()).unwrap().1, 4);
+    assert!(even.parse(b"5".to_stream()).is_err());
+}
+
+#[test]
+fn context_test() {
+    let bytes = b"host:x".to_stream();
+    let port = Byte::new(b':').then(number().context("port"));
+    let authority = Slice::new(b"host").then(port).context("authority");
+
+    let err = authority.parse(bytes).unwrap_err();
+    assert_eq!(err.offset(bytes), 5);
+    assert_eq!(err.context(), &["port", "authority"]);
+    assert_eq!(err.to_string(), "expected digit, found 'x' in port in authority");
+}
+
+#[test]
+fn mixed_test() {
+    // Cell methods take raw parsers and raw parsers take cells.
+    let sign = (byte(b'+') | Byte::new(b'-')).opt();
+    let signed = sign.then(number()).value(()).many0();
+
+    let (rest, xs) = signed.parse(b"+1-2 3".to_stream()).unwrap();
+    assert_eq!(xs.len(), 2);
+    assert_eq!(rest, b" 3");
+}
@@ -0,0 +1,138 @@
+use parsing::prelude::*;
+
+fn number<'a>() -> Cell<'a, impl Parse<'a, Output = String>> {
+    many1(any_digit()).map(|digits| digits.iter().collect::<String>())
+}
+
+fn unary(op: &str, x: String) -> String {
+    format!("({}{})", op, x)
+}
+
+fn binary(op: &str, l: String, r: String) -> String {
+    format!("({} {} {})", l, op, r)
+}
+
+fn arith<'a>() -> Cell<'a, impl Parse<'a, Output = String>> {
+    recursive(|expr| {
+        let atom = number().or(chr('(').then(expr).skip(chr(')')).map(|(_, e)| e));
+
+        pratt(atom)
+            .prefix(chr('-'), 5, |_, x| unary("-", x))
+            .postfix(chr('!'), 6, |_, x| format!("({}!)", x))
+            .infix(chr('+'), Assoc::Left, 1, |_, l, r| binary("+", l, r))
+            .infix(chr('-'), Assoc::Left, 1, |_, l, r| binary("-", l, r))
+            .infix(chr('*'), Assoc::Left, 2, |_, l, r| binary("*", l, r))
+            .infix(chr('^'), Assoc::Right, 3, |_, l, r| binary("^", l, r))
+            .boxed()
+    })
+}
+
+fn parse(s: &str) -> String {
+    let (rest, expr) = arith().parse(s.to_stream()).unwrap();
+    assert!(rest.is_empty(), "unparsed input {:?}", rest);
+    expr
+}
+
+#[test]
+fn pratt_precedence_test() {
+    assert_eq!(parse("1"), "1");
+    assert_eq!(parse("1+2*3"), "(1 + (2 * 3))");
+    assert_eq!(parse("1*2+3"), "((1 * 2) + 3)");
+    assert_eq!(parse("(1+2)*3"), "((1 + 2) * 3)");
+}
+
+#[test]
+fn pratt_associativity_test() {
+    assert_eq!(parse("1-2-3"), "((1 - 2) - 3)");
+    assert_eq!(parse("2^3^4"), "(2 ^ (3 ^ 4))");
+}
+
+#[test]
+fn pratt_prefix_postfix_test() {
+    assert_eq!(parse("-1*2"), "((-1) * 2)");
+    assert_eq!(parse("--1"), "(-(-1))");
+    assert_eq!(parse("-3!"), "(-(3!))");
+    assert_eq!(parse("2^3!"), "(2 ^ (3!))");
+    assert_eq!(parse("1-2!"), "(1 - (2!))");
+}
+
+#[test]
+fn pratt_trailing_input_test() {
+    let (rest, expr) = arith().parse("1+2)".to_stream()).unwrap();
+
+    assert_eq!(expr, "(1 + 2)");
+    assert_eq!(rest, b")");
+}
+
+#[test]
+fn pratt_error_test() {
+    let err = arith().parse("1+*2".to_stream()).unwrap_err();
+    assert_eq!(err.offset("1+*2".to_stream()), 2);
+
+    let err = arith().parse("".to_stream()).unwrap_err();
+    assert!(err.expected().contains(&Expected::Char('-')));
+}
+
+#[derive(Debug, PartialEq)]
+enum Filter {
+    Field(String),
+    Not(Box<Filter>),
+    Gt(Box<Filter>, Box<Filter>),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+}
+
+fn filter<'a>() -> Cell<'a, impl Parse<'a, &'a str, Output = Filter>, &'a str> {
+    let field = many1(alphanumeric()).map(|cs| Filter::Field(cs.into_iter().collect()));
+
+    pratt(field)
+        .prefix(chr('!'), 3, |_, f| Filter::Not(Box::new(f)))
+        .infix(slice(b"||"), Assoc::Left, 1, |_, l, r| {
+            Filter::Or(Box::new(l), Box::new(r))
+        })
+        .infix(slice(b"&&"), Assoc::Left, 2, |_, l, r| {
+            Filter::And(Box::new(l), Box::new(r))
+        })
+        .infix(chr('>'), Assoc::Left, 4, |_, l, r| {
+            Filter::Gt(Box::new(l), Box::new(r))
+        })
+}
+
+#[test]
+fn pratt_str_input_test() {
+    let (rest, f) = filter().parse("a>3||!b&&c").unwrap();
+
+    let field = |s: &str| Box::new(Filter::Field(s.to_string()));
+    assert_eq!(
+        f,
+        Filter::Or(
+            Box::new(Filter::Gt(field("a"), field("3"))),
+            Box::new(Filter::And(Box::new(Filter::Not(field("b"))), field("c"))),
+        )
+    );
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn pratt_zero_width_operator_test() {
+    // An optional operator matches nothing once the input is used up.
+    let bang = pratt(number()).postfix(opt(chr('!')), 1, |bang, x| match bang {
+        Some(_) => format!("({}!)", x),
+        None => x,
+    });
+    assert_eq!(bang.parse("3!!".to_stream()).unwrap().1, "((3!)!)");
+    assert_eq!(bang.parse("3".to_stream()).unwrap().1, "3");
+
+    let digits = many0(any_digit()).map(|digits| digits.iter().collect::<String>());
+    let product = pratt(digits).infix(opt(chr('*')), Assoc::Left, 1, |_, l, r| binary("*", l, r));
+    assert_eq!(product.parse("2".to_stream()).unwrap().1, "2");
+    assert_eq!(product.parse("2*3".to_stream()).unwrap().1, "(2 * 3)");
+
+    let minus = pratt(number()).prefix(opt(chr('-')), 5, |minus, x| match minus {
+        Some(_) => unary("-", x),
+        None => x,
+    });
+    assert_eq!(minus.parse("-3".to_stream()).unwrap().1, "(-3)");
+    assert_eq!(minus.parse("--3".to_stream()).unwrap().1, "(-(-3))");
+    assert_eq!(minus.parse("3".to_stream()).unwrap().1, "3");
+}
@@ -0,0 +1,62 @@
+use parsing::prelude::*;
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Num(u32),
+    Array(Vec<Value>),
+}
+
+fn value<'a>() -> Cell<'a, impl Parse<'a, Output = Value>> {
+    recursive(|value| {
+        let num = many1(any_digit()).map(|digits| {
+            Value::Num(digits.iter().collect::<String>().parse().unwrap())
+        });
+        let array = chr('[')
+            .then(value.sep_by0(chr(',')))
+            .skip(chr(']'))
+            .map(|(_, items)| Value::Array(items));
+
+        num.or(array)
+    })
+}
+
+#[test]
+fn recursive_array_test() {
+    let bytes = "[1,[2,[]],[[3]]]".to_stream();
+
+    let (rest, array) = value().parse(bytes).unwrap();
+
+    assert_eq!(
+        array,
+        Value::Array(vec![
+            Value::Num(1),
+            Value::Array(vec![Value::Num(2), Value::Array(vec![])]),
+            Value::Array(vec![Value::Array(vec![Value::Num(3)])]),
+        ])
+    );
+    assert!(rest.is_empty());
+
+    assert!(value().parse("[1,[2,x]]".to_stream()).is_err());
+}
+
+fn expr<'a>() -> Cell<'a, Boxed<'a, i64>> {
+    let atom = many1(any_digit())
+        .map(|digits| digits.iter().collect::<String>().parse::<i64>().unwrap())
+        .or(chr('(').then(lazy(expr)).skip(chr(')')).map(|(_, n)| n))
+        .boxed();
+
+    atom.clone()
+        .then(many0(chr('+').then(atom).map(|(_, n)| n)))
+        .map(|(n, rest)| n + rest.iter().sum::<i64>())
+        .boxed()
+}
+
+#[test]
+fn lazy_expr_test() {
+    let bytes = "1+(2+(3+4))+5".to_stream();
+
+    let (rest, sum) = expr().parse(bytes).unwrap();
+
+    assert_eq!(sum, 15);
+    assert!(rest.is_empty());
+}
@@ -0,0 +1,98 @@
+use parsing::prelude::*;
+
+fn digit<'a>() -> Cell<'a, impl Parse<'a, Output = u32>> {
+    any_digit().map(|ch| ch.to_digit(10).unwrap())
+}
+
+#[test]
+fn many0_empty_test() {
+    let empty = "".to_stream();
+
+    let (rest, xs) = many0(byte(b'a')).parse(empty).unwrap();
+    assert!(xs.is_empty());
+    assert!(rest.is_empty());
+
+    assert!(many1(byte(b'a')).parse(empty).is_err());
+    assert_eq!(opt(byte(b'a')).parse(empty).unwrap().1, None);
+    assert!(sep_by0(byte(b'a'), chr(',')).parse(empty).unwrap().1.is_empty());
+    assert!(sep_end_by(byte(b'a'), chr(',')).parse(empty).unwrap().1.is_empty());
+    assert_eq!(fold_many0(byte(b'a'), || 0, |n, _| n + 1).parse(empty).unwrap().1, 0);
+    assert!(many_m_n(byte(b'a'), 0, 2).parse(empty).unwrap().1.is_empty());
+    assert!(count(byte(b'a'), 0).parse(empty).unwrap().1.is_empty());
+}
+
+#[test]
+fn many_m_n_test() {
+    let bytes = "12345".to_stream();
+
+    let (rest, xs) = digit().many_m_n(2, 3).parse(bytes).unwrap();
+    assert_eq!(xs, vec![1, 2, 3]);
+    assert_eq!(rest, b"45");
+
+    let err = digit().many_m_n(2, 3).parse("1x".to_stream()).unwrap_err();
+    assert_eq!(err.offset("1x".to_stream()), 1);
+
+    let (rest, xs) = digit().count(4).parse(bytes).unwrap();
+    assert_eq!(xs, vec![1, 2, 3, 4]);
+    assert_eq!(rest, b"5");
+
+    assert!(digit().count(6).parse(bytes).is_err());
+    assert!(count(byte(b'a'), usize::MAX / 2).parse(b"aa".to_stream()).is_err());
+
+    let counted = digit().flat_map(|n| byte(b'a').count(n as usize * 1_000_000_000));
+    assert!(counted.parse(b"9aa".to_stream()).is_err());
+}
+
+#[test]
+fn sep_by_test() {
+    let bytes = "1,2,3,".to_stream();
+
+    let (rest, xs) = digit().sep_by1(chr(',')).parse(bytes).unwrap();
+    assert_eq!(xs, vec![1, 2, 3]);
+    assert_eq!(rest, b",");
+
+    let (rest, xs) = digit().sep_end_by(chr(',')).parse(bytes).unwrap();
+    assert_eq!(xs, vec![1, 2, 3]);
+    assert!(rest.is_empty());
+
+    let (rest, xs) = digit().sep_by0(chr(',')).parse(",1".to_stream()).unwrap();
+    assert!(xs.is_empty());
+    assert_eq!(rest, b",1");
+
+    assert!(digit().sep_by1(chr(',')).parse(",1".to_stream()).is_err());
+}
+
+#[test]
+fn many_till_test() {
+    let bytes = "ab*/rest".to_stream();
+
+    let (rest, (xs, end)) = any_char().many_till(slice(b"*/")).parse(bytes).unwrap();
+    assert_eq!(xs, vec!['a', 'b']);
+    assert_eq!(end, b"*/");
+    assert_eq!(rest, b"rest");
+
+    let err = any_char().many_till(slice(b"*/")).parse("ab1".to_stream()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected one of \"*/\", alphabetic character, found '1'"
+    );
+}
+
+#[test]
+fn fold_many0_test() {
+    let bytes = "4096 bytes".to_stream();
+
+    let (rest, n) = digit().fold_many0(|| 0, |n, d| n * 10 + d).parse(bytes).unwrap();
+
+    assert_eq!(n, 4096);
+    assert_eq!(rest, b" bytes");
+}
+
+#[test]
+fn opt_test() {
+    let header = slice(b"Host").skip(opt(chr(' '))).skip(chr(':'));
+
+    assert!(header.parse("Host:".to_stream()).is_ok());
+    assert!(header.parse("Host :".to_stream()).is_ok());
+    assert!(header.parse("Host-".to_stream()).is_err());
+}
@@ -0,0 +1,42 @@
+use parsing::prelude::*;
+
+#[test]
+fn location_test() {
+    let bytes = "GET / HTTP/1.1\r\nHost: example.com\r\n".to_stream();
+
+    assert_eq!(Location::new(bytes, 0), Location { line: 1, column: 1 });
+    assert_eq!(Location::new(bytes, 14), Location { line: 1, column: 15 });
+    assert_eq!(Location::new(bytes, 16), Location { line: 2, column: 1 });
+    assert_eq!(Location::new(bytes, 20), Location { line: 2, column: 5 });
+    assert_eq!(Location::new(bytes, 1000), Location { line: 3, column: 1 });
+}
+
+#[test]
+fn location_utf8_test() {
+    let bytes = "name = \"ünïcødé\"\nkey = 1".to_stream();
+    let offset = bytes.iter().position(|&b| b == b'\n').unwrap() - 1;
+
+    assert_eq!(Location::new(bytes, offset), Location { line: 1, column: 16 });
+}
+
+#[test]
+fn report_test() {
+    let bytes = "GET / HTTP/1.1\r\nHost example.com\r\n\r\n".to_stream();
+    let header = slice(b"GET / HTTP/1.1\r\n")
+        .then(take_until(chr(' ')))
+        .skip(chr(':'));
+
+    let err = header.parse(bytes).unwrap_err();
+    let report = Report::new(bytes, &err);
+
+    assert_eq!(report.offset(), 20);
+    assert_eq!(report.line(), b"Host example.com");
+    assert_eq!(
+        report.to_string(),
+        "error: expected ':', found ' '\n \
+         --> 2:5\n  \
+         |\n\
+         2 | Host example.com\n  \
+         |     ^"
+    );
+}
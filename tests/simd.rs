@@ -0,0 +1,389 @@
+use parsing::prelude::*;
+use parsing::simd::{self, Backend};
+
+fn available() -> impl Iterator<Item = Backend> {
+    Backend::ALL.iter().copied().filter(|b| b.is_available())
+}
+
+#[test]
+fn backend_cached_test() {
+    assert_eq!(simd::backend(), Backend::detect());
+    assert_eq!(simd::backend(), simd::backend());
+    assert!(simd::backend().is_available());
+    assert!(Backend::Scalar.is_available());
+    assert!(Backend::Swar.is_available());
+    assert_eq!(
+        Backend::Portable.is_available(),
+        cfg!(feature = "portable_simd")
+    );
+    assert_ne!(Backend::detect(), Backend::Scalar);
+}
+
+#[test]
+fn backend_find_test() {
+    let request = b"GET /index.html HTTP/1.1\r\nHost: example.com\r\n\r\n";
+
+    for backend in available() {
+        assert_eq!(backend.find(request, b" "), Some(3), "{:?}", backend);
+        assert_eq!(backend.find(request, b"\r\n"), Some(24), "{:?}", backend);
+        assert_eq!(backend.find(request, b"Host"), Some(26), "{:?}", backend);
+        assert_eq!(backend.find(request, b"\r\n\r\n"), Some(43), "{:?}", backend);
+        assert_eq!(backend.find(request, b"POST"), None, "{:?}", backend);
+    }
+}
+
+#[test]
+fn take_until_literal_backend_test() {
+    let bytes = "Host: example.com\r\nAccept: */*\r\n".to_stream();
+
+    for backend in available() {
+        let parser = TakeUntilLiteral::with_backend(b"\r\n", backend);
+
+        let (rest, host) = parser.parse(bytes).unwrap();
+        assert_eq!(host, b"Host: example.com");
+        assert_eq!(rest, b"\r\nAccept: */*\r\n");
+
+        let err = parser.parse("no line ending".to_stream()).unwrap_err();
+        assert_eq!(err.expected(), &[Expected::Slice(b"\r\n".to_vec().into())]);
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn cell_simd_path_test() {
+    let (rest, host) = parsing::cell::simd::take_until_literal(b"\r\n")
+        .parse(b"Host: example.com\r\n")
+        .unwrap();
+
+    assert_eq!(host, b"Host: example.com");
+    assert_eq!(rest, b"\r\n");
+}
+
+#[test]
+fn simd_slice_backend_test() {
+    for backend in available() {
+        let parser = parsing::parser::simd::Slice::with_backend(b"GET", backend);
+
+        let (rest, method) = parser.parse("GET / HTTP/1.1".to_stream()).unwrap();
+        assert_eq!(method, b"GET");
+        assert_eq!(rest, b" / HTTP/1.1");
+
+        assert!(parser.parse("PUT / HTTP/1.1".to_stream()).is_err());
+        assert!(parser.parse("GE".to_stream()).is_err());
+    }
+}
+
+#[test]
+fn streaming_take_until_literal_test() {
+    for backend in available() {
+        let parser = streaming::simd::TakeUntilLiteral::with_backend(b"\r\n", backend);
+
+        let (_, line) = parser.parse("GET / HTTP/1.1\r\n".to_stream()).unwrap();
+        assert_eq!(line, b"GET / HTTP/1.1");
+
+        let err = parser.parse("GET / HTT".to_stream()).unwrap_err();
+        assert_eq!(err.needed(), Some(Needed::Unknown));
+    }
+}
+
+fn naive(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    (0..=haystack.len())
+        .take_while(|&i| i + needle.len() <= haystack.len())
+        .find(|&i| &haystack[i..i + needle.len()] == needle)
+}
+
+/// Small deterministic generator so failures reproduce.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (self.0 >> 33) as usize
+    }
+
+    fn bytes(&mut self, len: usize, alphabet: &[u8]) -> Vec<u8> {
+        (0..len)
+            .map(|_| alphabet[self.next() % alphabet.len()])
+            .collect()
+    }
+}
+
+fn check(haystack: &[u8], needle: &[u8]) {
+    for backend in available() {
+        assert_eq!(
+            backend.find(haystack, needle),
+            naive(haystack, needle),
+            "{:?} haystack {:?} needle {:?}",
+            backend,
+            haystack.escape_ascii().to_string(),
+            needle.escape_ascii().to_string(),
+        );
+    }
+}
+
+#[test]
+fn find_chunk_boundary_test() {
+    let step = if cfg!(miri) { 7 } else { 1 };
+
+    for needle_len in (1..40).step_by(step) {
+        let needle: Vec<u8> = (0..needle_len as u8).map(|b| b'0' + b).collect();
+
+        for start in (0..80).step_by(step) {
+            let mut haystack = vec![b'.'; start];
+            haystack.extend_from_slice(&needle);
+            haystack.extend_from_slice(b"..");
+
+            check(&haystack, &needle);
+            check(&haystack[..start + needle_len], &needle);
+            check(&haystack[..start + needle_len - 1], &needle);
+        }
+    }
+}
+
+#[test]
+fn find_partial_match_test() {
+    check(b"aaaaaaaaaaaaaaaaaaaaaab", b"aab");
+    check(b"..............\r\r\n", b"\r\n");
+    check(b"...............GET / ", b"GET /");
+    check(b"...............GE", b"GET");
+    check(b"abcabcabcabcabcabcabcabcabcabd", b"abcabd");
+    check(b"", b"a");
+    check(b"a", b"");
+}
+
+#[test]
+fn find_differential_test() {
+    let mut rng = Lcg(0x5eed);
+    let rounds = if cfg!(miri) { 40 } else { 4000 };
+
+    for _ in 0..rounds {
+        let len = rng.next() % 100;
+        let haystack = rng.bytes(len, b"ab\r\n");
+        let needle = match rng.next() % 2 {
+            0 if !haystack.is_empty() => {
+                let start = rng.next() % haystack.len();
+                let len = 1 + rng.next() % 40.min(haystack.len() - start);
+                haystack[start..start + len].to_vec()
+            }
+            _ => {
+                let len = 1 + rng.next() % 40;
+                rng.bytes(len, b"ab\r\n")
+            }
+        };
+
+        check(&haystack, &needle);
+    }
+}
+
+#[test]
+fn find_long_needle_test() {
+    let boundary = b"\r\n--boundary1234567890";
+    let mut body = b"--boundary1234567890\r\n\r\nfield".to_vec();
+    body.extend_from_slice(&[b'-'; 70]);
+    body.extend_from_slice(b"\r\n--boundary123456789");
+    body.extend_from_slice(boundary);
+    body.extend_from_slice(b"--\r\n");
+
+    check(&body, boundary);
+    check(&body, &body[3..60]);
+    check(&body[..body.len() - 5], boundary);
+    check(&body[..body.len() - 6], boundary);
+
+    let (rest, part) = take_until_literal(boundary).parse(&body[..]).unwrap();
+    assert_eq!(part.len(), body.len() - boundary.len() - 4);
+    assert!(rest.starts_with(boundary));
+}
+
+fn token_set() -> ByteSet {
+    ByteSet::from_ranges(&[
+        b'0'..=b'9',
+        b'a'..=b'z',
+        b'A'..=b'Z',
+        b'!'..=b'!',
+        b'#'..=b'\'',
+        b'*'..=b'+',
+        b'-'..=b'.',
+        b'^'..=b'`',
+    ])
+}
+
+#[test]
+fn byte_set_test() {
+    let set = ByteSet::new(b" \t\r\n");
+
+    assert!(set.contains(b'\t'));
+    assert!(!set.contains(b'a'));
+    assert_eq!(set, ByteSet::from_ranges(&[b'\t'..=b'\n', b'\r'..=b'\r', b' '..=b' ']));
+}
+
+#[test]
+fn find_class_test() {
+    let line = b"Content-Type: text/html; charset=utf-8\r\n";
+    let token = token_set();
+    let crlf = ByteSet::new(b"\r\n");
+
+    for backend in available() {
+        assert_eq!(backend.find_not(line, &token), Some(12), "{:?}", backend);
+        assert_eq!(backend.find_any(line, &crlf), Some(38), "{:?}", backend);
+        assert_eq!(backend.find_any(b"no line ending", &crlf), None);
+        assert_eq!(backend.find_not(b"", &token), None);
+    }
+}
+
+fn naive_class(haystack: &[u8], set: &ByteSet, negate: bool) -> Option<usize> {
+    haystack.iter().position(|&b| set.contains(b) != negate)
+}
+
+#[test]
+fn find_class_differential_test() {
+    let mut rng = Lcg(0xc1a55);
+    let rounds = if cfg!(miri) { 40 } else { 2000 };
+
+    for round in 0..rounds {
+        // Every eighth set is too fragmented for the range comparisons.
+        let members = if round % 8 == 0 { 40 } else { 1 + rng.next() % 12 };
+        let set_bytes: Vec<u8> = (0..members).map(|_| rng.next() as u8).collect();
+        let set = ByteSet::new(&set_bytes);
+
+        let len = rng.next() % 100;
+        let haystack: Vec<u8> = (0..len)
+            .map(|_| match rng.next() % 3 {
+                0 => set_bytes[rng.next() % set_bytes.len()],
+                _ => rng.next() as u8,
+            })
+            .collect();
+
+        for backend in available() {
+            assert_eq!(
+                backend.find_any(&haystack, &set),
+                naive_class(&haystack, &set, false),
+                "{:?} {:?} {:?}",
+                backend,
+                set_bytes,
+                haystack
+            );
+            assert_eq!(
+                backend.find_not(&haystack, &set),
+                naive_class(&haystack, &set, true),
+                "{:?} {:?} {:?}",
+                backend,
+                set_bytes,
+                haystack
+            );
+        }
+    }
+}
+
+#[test]
+fn find_class_range_ends_test() {
+    let haystack: Vec<u8> = (0..=255).rev().chain(0..=255).collect();
+    let sets = [
+        ByteSet::from_ranges(&[0..=0]),
+        ByteSet::from_ranges(&[255..=255]),
+        ByteSet::from_ranges(&[0x7f..=0x80]),
+        ByteSet::from_ranges(&[0..=0x7f, 0xc0..=0xff]),
+        ByteSet::from_ranges(&[0..=255]),
+    ];
+    let step = if cfg!(miri) { 61 } else { 1 };
+
+    for set in &sets {
+        for start in (0..haystack.len()).step_by(step) {
+            let haystack = &haystack[start..];
+
+            for backend in available() {
+                assert_eq!(
+                    backend.find_any(haystack, set),
+                    naive_class(haystack, set, false),
+                    "{:?} {:?}",
+                    backend,
+                    set
+                );
+                assert_eq!(
+                    backend.find_not(haystack, set),
+                    naive_class(haystack, set, true),
+                    "{:?} {:?}",
+                    backend,
+                    set
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn take_while_set_test() {
+    let bytes = "Content-Type: text/html\r\n".to_stream();
+
+    for backend in available() {
+        let name = TakeWhileSet::with_backend(token_set(), backend);
+        let value = TakeTillAny::with_backend(ByteSet::new(b"\r\n"), backend);
+
+        let (rest, field) = name.parse(bytes).unwrap();
+        assert_eq!(field, b"Content-Type");
+        assert_eq!(rest, b": text/html\r\n");
+
+        let (rest, text) = value.parse(&rest[2..]).unwrap();
+        assert_eq!(text, b"text/html");
+        assert_eq!(rest, b"\r\n");
+
+        let (rest, empty) = name.parse(b": x".to_stream()).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(rest, b": x");
+
+        let (rest, all) = value.parse("no line ending").unwrap();
+        assert_eq!(all, "no line ending");
+        assert!(rest.is_empty());
+    }
+
+    let (rest, method) = take_while_set(token_set()).parse("GET / HTTP/1.1").unwrap();
+    assert_eq!(method, "GET");
+    assert_eq!(rest, " / HTTP/1.1");
+
+    let (_, path) = take_till_any(ByteSet::new(b" ?")).parse("/a/b?c=d HTTP/1.1").unwrap();
+    assert_eq!(path, "/a/b");
+}
+
+#[test]
+fn str_char_boundary_test() {
+    // "é" is encoded as C3 A9, the literals only occur inside it.
+    for backend in available() {
+        let parser = TakeUntilLiteral::with_backend(b"\xA9", backend);
+        let err = parser.parse("café").unwrap_err();
+        assert_eq!(err.remaining(), 0);
+
+        let parser = TakeUntilLiteral::with_backend(b"\xA9 ", backend);
+        assert_eq!(parser.parse("é é".as_bytes()).unwrap().1, b"\xC3");
+        assert!(parser.parse("é é").is_err());
+
+        let parser = streaming::simd::TakeUntilLiteral::with_backend(b"\xA9", backend);
+        let err = parser.parse("é").unwrap_err();
+        assert!(!err.is_incomplete());
+        assert!(err.is_recoverable());
+
+        let parser = parsing::parser::simd::Slice::with_backend(b"\xC3", backend);
+        assert!(parser.parse("é").is_err());
+    }
+}
+
+#[test]
+fn class_char_boundary_test() {
+    // "é" is encoded as C3 A9.
+    let input = "caé";
+
+    for backend in available() {
+        let letters = TakeWhileSet::with_backend(ByteSet::new(b"ac\xC3"), backend);
+        let err = letters.parse(input).unwrap_err();
+        assert_eq!(err.offset(input), 2);
+        assert_eq!(err.found(), Some(&Found::Char('é')));
+
+        let (_, bytes) = letters.parse(input.as_bytes()).unwrap();
+        assert_eq!(bytes, b"ca\xC3");
+
+        let till = TakeTillAny::with_backend(ByteSet::new(b"\xA9"), backend);
+        assert_eq!(till.parse(input).unwrap_err().offset(input), 2);
+        assert_eq!(till.parse("ca").unwrap(), ("", "ca"));
+    }
+}
@@ -0,0 +1,53 @@
+use parsing::prelude::*;
+
+#[test]
+fn spanned_test() {
+    let source = "GET /index.html HTTP/1.1\r\nHost: example.com\r\n".to_stream();
+    let input = Located::new(source);
+
+    let method = slice(b"GET").spanned();
+    let path = take_until(chr(' ')).spanned();
+    let header = take_until(chr(':')).spanned();
+
+    let (input, (_, method)) = method.parse(input).unwrap();
+    let (input, (path, path_span)) = chr(' ').then(path).map(|(_, path)| path).parse(input).unwrap();
+    let (input, _) = take_until(slice(b"\r\n")).skip(slice(b"\r\n")).parse(input).unwrap();
+    let (input, (name, name_span)) = header.parse(input).unwrap();
+
+    assert_eq!(method, Span::new(0, 3));
+    assert_eq!(*path.fragment(), b"/index.html");
+    assert_eq!(path_span, Span::new(4, 15));
+    assert_eq!(path.span(), path_span);
+    assert_eq!(*name.fragment(), b"Host");
+    assert_eq!(name_span, Span::new(26, 30));
+    assert_eq!(name_span.location(source), Location { line: 2, column: 1 });
+    assert_eq!(input.offset(), 30);
+}
+
+#[test]
+fn located_str_test() {
+    let input = Located::new("λx → x");
+
+    let (input, (_, lambda)) = chr('λ').spanned().parse(input).unwrap();
+    let (input, (_, arrow)) = take_until(chr('→'))
+        .then(chr('→').spanned())
+        .map(|(_, arrow)| arrow)
+        .parse(input)
+        .unwrap();
+
+    assert_eq!(lambda, Span::new(0, 2));
+    assert_eq!(arrow, Span::new(4, 7));
+    assert_eq!(*input.fragment(), " x");
+    assert_eq!(input.offset(), 7);
+}
+
+#[test]
+fn recognize_test() {
+    let bytes = "HTTP/1.1\r\n".to_stream();
+    let version = slice(b"HTTP/").then(any_digit()).then(chr('.')).then(any_digit());
+
+    let (rest, output) = version.recognize().parse(bytes).unwrap();
+
+    assert_eq!(output, b"HTTP/1.1");
+    assert_eq!(rest, b"\r\n");
+}
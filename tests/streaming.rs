@@ -0,0 +1,68 @@
+use parsing::prelude::*;
+
+#[test]
+fn slice_incomplete_test() {
+    let parser = streaming::slice(b"\r\n\r\n");
+
+    let err = parser.parse(b"\r\n\r").unwrap_err();
+    assert!(err.is_incomplete());
+    assert_eq!(err.needed(), Some(Needed::Size(1)));
+
+    let err = parser.parse(b"\r\nX").unwrap_err();
+    assert!(!err.is_incomplete());
+
+    assert!(parser.parse(b"\r\n\r\nbody").is_ok());
+}
+
+#[test]
+fn or_propagates_incomplete_test() {
+    let method = streaming::slice(b"GET").or(streaming::slice(b"HEAD"));
+
+    let err = method.parse(b"HE").unwrap_err();
+    assert_eq!(err.needed(), Some(Needed::Size(2)));
+
+    let err = method.parse(b"GE").unwrap_err();
+    assert_eq!(err.needed(), Some(Needed::Size(1)));
+}
+
+#[test]
+fn many_incomplete_test() {
+    let parser = many1(streaming::byte(b'a'));
+
+    let err = parser.parse(b"aaa").unwrap_err();
+    assert_eq!(err.needed(), Some(Needed::Size(1)));
+
+    let (rest, xs) = parser.parse(b"aaab").unwrap();
+    assert_eq!(xs.len(), 3);
+    assert_eq!(rest, b"b");
+
+    let err = streaming::complete(parser).parse(b"aaa").unwrap_err();
+    assert!(!err.is_incomplete());
+}
+
+fn header_block<'a>() -> impl Parse<'a, Output = &'a [u8]> {
+    take_until(streaming::slice(b"\r\n\r\n")).skip(streaming::slice(b"\r\n\r\n"))
+}
+
+#[test]
+fn buffered_header_block_test() {
+    let mut buffer = Vec::new();
+
+    buffer.extend_from_slice(b"GET /index.html HTTP/1.1\r\nHost: exa");
+    let err = header_block().parse(&buffer).unwrap_err();
+    assert!(err.is_incomplete());
+
+    buffer.extend_from_slice(b"mple.com\r\n\r");
+    let err = header_block().parse(&buffer).unwrap_err();
+    assert_eq!(err.needed(), Some(Needed::Size(1)));
+
+    buffer.extend_from_slice(b"\nbody");
+    let (rest, block) = header_block().parse(&buffer).unwrap();
+    assert_eq!(block, b"GET /index.html HTTP/1.1\r\nHost: example.com");
+    assert_eq!(rest, b"body");
+
+    let err = streaming::complete(header_block())
+        .parse(b"GET / HTTP/1.1\r\n")
+        .unwrap_err();
+    assert!(!err.is_incomplete());
+}
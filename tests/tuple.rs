@@ -0,0 +1,104 @@
+use parsing::prelude::*;
+
+fn to_str(bytes: &[u8]) -> &str {
+    std::str::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn seq_test() {
+    let bytes = b"GET /index.html HTTP/1.1\r\n".to_stream();
+    let line = seq((
+        take_until(chr(' ')).map(to_str),
+        chr(' '),
+        take_until(chr(' ')).map(to_str),
+        chr(' '),
+        take_until(slice(b"\r\n")).map(to_str),
+        slice(b"\r\n"),
+    ));
+
+    let (rest, (method, _, path, _, version, _)) = line.parse(bytes).unwrap();
+    assert_eq!((method, path, version), ("GET", "/index.html", "HTTP/1.1"));
+    assert!(rest.is_empty());
+
+    let err = line.parse(b"GET /index.html".to_stream()).unwrap_err();
+    assert_eq!(err.remaining(), 0);
+}
+
+#[test]
+fn seq_twelve_test() {
+    let items = seq((
+        any_digit(),
+        any_digit(),
+        any_digit(),
+        any_digit(),
+        any_digit(),
+        any_digit(),
+        any_digit(),
+        any_digit(),
+        any_digit(),
+        any_digit(),
+        any_char(),
+        any_char(),
+    ));
+
+    let (rest, output) = items.parse(b"0123456789ab!".to_stream()).unwrap();
+    assert_eq!(output.0, '0');
+    assert_eq!(output.11, 'b');
+    assert_eq!(rest, b"!");
+}
+
+#[test]
+fn alt_test() {
+    let method = alt((
+        slice(b"GET"),
+        slice(b"HEAD"),
+        slice(b"POST"),
+        slice(b"PUT"),
+        slice(b"DELETE"),
+        slice(b"CONNECT"),
+        slice(b"OPTIONS"),
+        slice(b"TRACE"),
+        slice(b"PATCH"),
+    ));
+
+    assert_eq!(
+        method.parse(b"TRACE /".to_stream()).unwrap(),
+        (&b" /"[..], &b"TRACE"[..])
+    );
+
+    let bytes = b"BREW /".to_stream();
+    let err = method.parse(bytes).unwrap_err();
+    assert_eq!(err.offset(bytes), 0);
+    assert_eq!(err.expected().len(), 9);
+}
+
+#[test]
+fn alt_cut_test() {
+    let parser = alt((
+        byte(b'(') >> cut(byte(b')')),
+        byte(b'(') >> byte(b'x'),
+    ));
+
+    assert!(parser.parse(b"()".to_stream()).is_ok());
+
+    let err = parser.parse(b"(x".to_stream()).unwrap_err();
+    assert!(err.is_fatal());
+}
+
+#[test]
+fn permutation_test() {
+    let field = |name: &'static [u8]| {
+        (slice(name) >> take_until(chr(';')).map(to_str)).skip(chr(';'))
+    };
+    let fields = permutation((field(b"a="), field(b"b="), field(b"c=")));
+
+    let (rest, (a, b, c)) = fields.parse(b"c=3;a=1;b=2;rest".to_stream()).unwrap();
+    assert_eq!((a, b, c), ("1", "2", "3"));
+    assert_eq!(rest, b"rest");
+
+    // Every parser has to match once.
+    let bytes = b"a=1;c=3;c=4;".to_stream();
+    let err = fields.parse(bytes).unwrap_err();
+    assert_eq!(err.offset(bytes), 8);
+    assert!(fields.parse(b"a=1;b=2;".to_stream()).is_err());
+}
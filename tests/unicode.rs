@@ -0,0 +1,104 @@
+use parsing::prelude::*;
+
+#[test]
+fn utf8_bytes_test() {
+    let bytes = "ünï-1".to_stream();
+    let word = many1(alphabetic());
+
+    let (rest, chars) = word.parse(bytes).unwrap();
+
+    assert_eq!(chars, vec!['ü', 'n', 'ï']);
+    assert_eq!(rest, b"-1");
+}
+
+#[test]
+fn chr_multibyte_test() {
+    let bytes = "é:".to_stream();
+
+    let (rest, ch) = chr('é').skip(chr(':')).parse(bytes).unwrap();
+    assert_eq!(ch, 'é');
+    assert!(rest.is_empty());
+
+    assert!(chr('Ã').parse(bytes).is_err());
+}
+
+#[test]
+fn invalid_utf8_test() {
+    let bytes = b"\xffabc".to_stream();
+
+    let err = any_scalar().parse(bytes).unwrap_err();
+    assert_eq!(err.offset(bytes), 0);
+    assert_eq!(err.to_string(), "expected character, found '\\xff'");
+
+    let truncated = b"\xe2\x82".to_stream();
+    assert!(any_scalar().parse(truncated).is_err());
+
+    let latin1 = b"\xb2".to_stream();
+    assert!(any_digit().parse(latin1).is_err());
+}
+
+#[test]
+fn classes_test() {
+    let input = "Δx = 3\u{a0}km";
+
+    let (rest, upper) = category(Category::Uppercase).parse(input).unwrap();
+    assert_eq!((upper, rest), ('Δ', "x = 3\u{a0}km"));
+
+    let (rest, ch) = char_range('a'..='z').parse(rest).unwrap();
+    assert_eq!((ch, rest), ('x', " = 3\u{a0}km"));
+
+    let (rest, _) = whitespace().then(one_of("=:")).then(whitespace()).parse(rest).unwrap();
+    let (rest, digit) = numeric().parse(rest).unwrap();
+    assert_eq!(digit, '3');
+
+    let (rest, space) = whitespace().parse(rest).unwrap();
+    assert_eq!((space, rest), ('\u{a0}', "km"));
+
+    let (rest, unit) = many1(none_of(" \n")).parse(rest).unwrap();
+    assert_eq!(unit, vec!['k', 'm']);
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn class_error_test() {
+    let err = one_of("+-").parse("*").unwrap_err();
+    assert_eq!(err.to_string(), "expected one of '+', '-', found '*'");
+
+    let err = char_range('0'..='7').parse("9").unwrap_err();
+    assert_eq!(err.to_string(), "expected '0'..='7', found '9'");
+
+    let err = none_of("\"\\").parse("\"").unwrap_err();
+    assert_eq!(err.to_string(), "expected character other than '\"', '\\\\', found '\"'");
+}
+
+#[test]
+fn streaming_truncated_test() {
+    let bytes = b"\xc3".to_stream();
+
+    let err = streaming::chr('é').parse(bytes).unwrap_err();
+    assert_eq!(err.needed(), Some(Needed::Size(1)));
+}
+
+#[test]
+fn general_category_test() {
+    assert_eq!(GeneralCategory::of('A'), GeneralCategory::Lu);
+    assert_eq!(GeneralCategory::of('ß'), GeneralCategory::Ll);
+    assert_eq!(GeneralCategory::of('ǅ'), GeneralCategory::Lt);
+    assert_eq!(GeneralCategory::of('٣'), GeneralCategory::Nd);
+    assert_eq!(GeneralCategory::of('Ⅻ'), GeneralCategory::Nl);
+    assert_eq!(GeneralCategory::of('\u{301}'), GeneralCategory::Mn);
+    assert_eq!(GeneralCategory::of('«'), GeneralCategory::Pi);
+    assert_eq!(GeneralCategory::of('€'), GeneralCategory::Sc);
+    assert_eq!(GeneralCategory::of('\u{2028}'), GeneralCategory::Zl);
+    assert_eq!(GeneralCategory::of('\u{e000}'), GeneralCategory::Co);
+    assert_eq!(GeneralCategory::of('\u{10ffff}'), GeneralCategory::Cn);
+
+    // Decimal digits, but not the other numerics `is_numeric` accepts.
+    let digits = many1(general_category(GeneralCategory::Nd));
+    let (rest, chars) = digits.parse("٣3½").unwrap();
+    assert_eq!(chars, vec!['٣', '3']);
+    assert_eq!(rest, "½");
+
+    let err = general_category(GeneralCategory::Lu).parse("a").unwrap_err();
+    assert_eq!(err.to_string(), "expected character in category Lu, found 'a'");
+}